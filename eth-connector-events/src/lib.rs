@@ -0,0 +1,207 @@
+//! NEP-297 event payloads emitted by `pure-eth-connector`, for indexers.
+//!
+//! The connector logs every event as `EVENT_JSON:{"standard":..,"version":..,"event":..,"data":[..]}`.
+//! Balances are serialized as decimal strings to avoid precision loss in JSON consumers.
+//!
+//! With the `simulation` feature, [`simulation`] models the connector accounting.
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "simulation")]
+pub mod simulation;
+
+pub const EVENT_JSON_PREFIX: &str = "EVENT_JSON:";
+pub const EVENT_STANDARD: &str = "eth-connector";
+pub const EVENT_VERSION: &str = "1.0.0";
+
+pub type AccountId = String;
+pub type Balance = u128;
+
+/// NEP-297 event envelope.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct EventLog {
+    pub standard: String,
+    pub version: String,
+    #[serde(flatten)]
+    pub event: ConnectorEvent,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "event", content = "data", rename_all = "snake_case")]
+pub enum ConnectorEvent {
+    DepositSubmitted(Vec<DepositSubmitted>),
+    Deposit(Vec<Deposit>),
+    DepositFeeSplit(Vec<DepositFeeSplit>),
+    DepositStuck(Vec<DepositStuck>),
+    DepositDeferred(Vec<DepositDeferred>),
+    DepositRolledBack(Vec<DepositRolledBack>),
+    DepositTransferFallback(Vec<DepositTransferFallback>),
+    EthRecipientBlock(Vec<EthRecipientBlock>),
+    ProofRevoked(Vec<ProofRevoked>),
+    ProverDisagreement(Vec<ProverDisagreement>),
+    SupplyDiscrepancy(Vec<SupplyDiscrepancy>),
+    Withdraw(Vec<Withdraw>),
+}
+
+/// Deposit proof submitted, `trace_id` follows it through the rest of the flow.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct DepositSubmitted {
+    pub trace_id: String,
+    pub proof_key: String,
+    pub relayer_id: AccountId,
+}
+
+/// Deposit finalized, attributed to the account which submitted its proof.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Deposit {
+    pub proof_key: String,
+    pub new_owner_id: AccountId,
+    #[serde(with = "u128_dec_format")]
+    pub amount: Balance,
+    #[serde(with = "u128_dec_format")]
+    pub fee: Balance,
+    /// Protocol fee accrued by the connector, on top of the relayer `fee`.
+    #[serde(with = "u128_dec_format")]
+    pub protocol_fee: Balance,
+    pub relayer_id: AccountId,
+    pub trace_id: String,
+    /// Short hash of the connector configuration the deposit was processed under.
+    pub config_hash: String,
+}
+
+/// Tokens burned for a withdrawal to Ethereum.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Withdraw {
+    pub owner_id: AccountId,
+    /// Hex encoded address, without the `0x` prefix.
+    pub eth_recipient: String,
+    #[serde(with = "u128_dec_format")]
+    pub amount: Balance,
+    /// Part of `amount` kept by the connector, the rest is released on Ethereum.
+    #[serde(with = "u128_dec_format")]
+    pub fee: Balance,
+    /// Short hash of the connector configuration the withdrawal was processed under.
+    pub config_hash: String,
+}
+
+/// Deposit fee distributed between the treasury and the relayer which submitted the proof.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct DepositFeeSplit {
+    pub treasury_id: AccountId,
+    #[serde(with = "u128_dec_format")]
+    pub treasury_fee: Balance,
+    pub relayer_id: AccountId,
+    #[serde(with = "u128_dec_format")]
+    pub relayer_fee: Balance,
+}
+
+/// Deposit left retryable because its prover call didn't succeed.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct DepositStuck {
+    pub proof_key: String,
+    pub relayer_id: AccountId,
+    pub trace_id: String,
+}
+
+/// Verified deposit left for `complete_deposit` as too little gas was left to mint it.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct DepositDeferred {
+    pub proof_key: String,
+    pub trace_id: String,
+}
+
+/// Engine deposit undone after the engine failed to credit the recipient,
+/// its proof can be submitted again.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct DepositRolledBack {
+    pub proof_key: String,
+    pub trace_id: String,
+    pub engine_account_id: AccountId,
+}
+
+/// Deposit message not handled by the recipient, which keeps the tokens as for
+/// a deposit without a message.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct DepositTransferFallback {
+    pub trace_id: String,
+    pub receiver_id: AccountId,
+    #[serde(with = "u128_dec_format")]
+    pub amount: Balance,
+}
+
+/// Accepted proof revoked after its Ethereum block was orphaned by a reorg.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ProofRevoked {
+    pub proof_key: String,
+    pub clawback_account_id: Option<AccountId>,
+    /// Amount burned from `clawback_account_id`.
+    #[serde(with = "u128_dec_format")]
+    pub clawed_back: Balance,
+}
+
+/// Shadow prover result differing from the primary prover one.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ProverDisagreement {
+    pub proof_key: String,
+    pub shadow_prover_id: AccountId,
+    /// Result of the primary prover, which gated the deposit.
+    pub verified: bool,
+    /// `None` when the shadow prover call failed.
+    pub shadow_verified: Option<bool>,
+}
+
+/// Withdrawals to an Ethereum address were blocked or unblocked by the admin.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct EthRecipientBlock {
+    /// Hex encoded address, without the `0x` prefix.
+    pub eth_address: String,
+    pub blocked: bool,
+}
+
+/// Minted-minus-burned totals don't match the token total supply.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SupplyDiscrepancy {
+    #[serde(with = "u128_dec_format")]
+    pub total_minted: Balance,
+    #[serde(with = "u128_dec_format")]
+    pub total_burned: Balance,
+    #[serde(with = "u128_dec_format")]
+    pub total_supply: Balance,
+}
+
+impl EventLog {
+    /// Parse a receipt log line. Returns `None` for logs which are not NEP-297 events.
+    pub fn from_log(log: &str) -> Option<Result<Self, serde_json::Error>> {
+        log.strip_prefix(EVENT_JSON_PREFIX)
+            .map(serde_json::from_str)
+    }
+
+    /// Format as a receipt log line, the way the connector emits it.
+    pub fn to_log(&self) -> String {
+        format!(
+            "{}{}",
+            EVENT_JSON_PREFIX,
+            serde_json::to_string(self).unwrap()
+        )
+    }
+}
+
+mod u128_dec_format {
+    use serde::de;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S>(num: &u128, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&num.to_string())
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<u128, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(de::Error::custom)
+    }
+}
@@ -0,0 +1,151 @@
+//! Pure-Rust model of the connector accounting, for property-testing relayer logic
+//! in CI without a NEAR node.
+//!
+//! Mirrors the fee split, pause and proof deduplication rules of `pure-eth-connector`,
+//! keep both in sync. The fee rounding is the connector's own code.
+use crate::{AccountId, Balance};
+use std::collections::{BTreeMap, BTreeSet};
+
+#[path = "../../pure-eth-connector/src/bps.rs"]
+mod bps;
+
+pub use bps::{bps_fee_ceil, bps_payout_floor, BPS_DENOMINATOR};
+pub const PAUSE_DEPOSIT: u8 = 1 << 0;
+pub const PAUSE_WITHDRAW: u8 = 1 << 1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModelError {
+    DepositPaused,
+    WithdrawPaused,
+    ProofAlreadyUsed,
+    FeeExceedsAmount,
+    NotEnoughBalance,
+    BlockedEthRecipient,
+    WithdrawBelowMinimum,
+}
+
+/// Amounts minted by a deposit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DepositOutcome {
+    pub recipient_amount: Balance,
+    pub treasury_fee: Balance,
+    pub relayer_fee: Balance,
+    pub protocol_fee: Balance,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ConnectorModel {
+    /// Account finishing deposits, which collects the fee not paid to relayers.
+    pub treasury_id: AccountId,
+    pub relayer_fee_share_bps: u16,
+    pub deposit_fee_bps: u16,
+    /// Protocol fees collected by deposits and withdrawals and not yet claimed.
+    pub accrued_fees: Balance,
+    pub withdraw_fee_bps: u16,
+    pub min_withdraw_amount: Balance,
+    pub paused_mask: u8,
+    pub balances: BTreeMap<AccountId, Balance>,
+    pub total_supply: Balance,
+    pub used_proofs: BTreeSet<String>,
+    /// Hex encoded Ethereum addresses, without the `0x` prefix.
+    pub blocked_eth_recipients: BTreeSet<String>,
+}
+
+impl ConnectorModel {
+    pub fn new(treasury_id: &str) -> Self {
+        Self {
+            treasury_id: treasury_id.into(),
+            ..Default::default()
+        }
+    }
+
+    pub fn balance_of(&self, account_id: &str) -> Balance {
+        self.balances.get(account_id).copied().unwrap_or(0)
+    }
+
+    /// Finish a deposit whose proof was verified by the prover.
+    pub fn deposit(
+        &mut self,
+        proof_key: &str,
+        new_owner_id: &str,
+        amount: Balance,
+        fee: Balance,
+        relayer_id: &str,
+    ) -> Result<DepositOutcome, ModelError> {
+        if self.paused_mask & PAUSE_DEPOSIT != 0 {
+            return Err(ModelError::DepositPaused);
+        }
+        if fee >= amount {
+            return Err(ModelError::FeeExceedsAmount);
+        }
+        if self.used_proofs.contains(proof_key) {
+            return Err(ModelError::ProofAlreadyUsed);
+        }
+        self.used_proofs.insert(proof_key.into());
+
+        let relayer_fee = if relayer_id == self.treasury_id {
+            0
+        } else {
+            bps_payout_floor(fee, self.relayer_fee_share_bps)
+        };
+        let protocol_fee = bps_fee_ceil(amount - fee, self.deposit_fee_bps);
+        let outcome = DepositOutcome {
+            recipient_amount: amount - fee - protocol_fee,
+            treasury_fee: fee - relayer_fee,
+            relayer_fee,
+            protocol_fee,
+        };
+        self.accrued_fees += protocol_fee;
+        self.mint(new_owner_id, outcome.recipient_amount);
+        let treasury_id = self.treasury_id.clone();
+        self.mint(&treasury_id, outcome.treasury_fee);
+        self.mint(relayer_id, outcome.relayer_fee);
+        Ok(outcome)
+    }
+
+    /// Mint the accrued protocol fees to `recipient_id`.
+    pub fn claim_fees(&mut self, recipient_id: &str) -> Balance {
+        let amount = self.accrued_fees;
+        self.accrued_fees = 0;
+        self.mint(recipient_id, amount);
+        amount
+    }
+
+    /// Burn tokens of `owner_id` for a withdrawal to `eth_recipient`, returning
+    /// the amount released on Ethereum.
+    pub fn withdraw(
+        &mut self,
+        owner_id: &str,
+        eth_recipient: &str,
+        amount: Balance,
+    ) -> Result<Balance, ModelError> {
+        if self.paused_mask & PAUSE_WITHDRAW != 0 {
+            return Err(ModelError::WithdrawPaused);
+        }
+        if self
+            .blocked_eth_recipients
+            .contains(&eth_recipient.to_lowercase())
+        {
+            return Err(ModelError::BlockedEthRecipient);
+        }
+        if amount < self.min_withdraw_amount {
+            return Err(ModelError::WithdrawBelowMinimum);
+        }
+        let balance = self.balance_of(owner_id);
+        if balance < amount {
+            return Err(ModelError::NotEnoughBalance);
+        }
+        self.balances.insert(owner_id.into(), balance - amount);
+        self.total_supply -= amount;
+        let fee = bps_fee_ceil(amount, self.withdraw_fee_bps);
+        self.accrued_fees += fee;
+        Ok(amount - fee)
+    }
+
+    fn mint(&mut self, account_id: &str, amount: Balance) {
+        if amount > 0 {
+            *self.balances.entry(account_id.into()).or_insert(0) += amount;
+            self.total_supply += amount;
+        }
+    }
+}
@@ -0,0 +1,219 @@
+use eth_connector_events::*;
+
+/// Log line as formatted by `pure-eth-connector`'s `events::emit_deposit_fee_split`.
+const DEPOSIT_FEE_SPLIT_LOG: &str = r#"EVENT_JSON:{"standard":"eth-connector","version":"1.0.0","event":"deposit_fee_split","data":[{"treasury_id":"eth_connector.root","treasury_fee":"225","relayer_id":"root","relayer_fee":"225"}]}"#;
+
+#[test]
+fn test_parse_deposit_fee_split() {
+    let log = EventLog::from_log(DEPOSIT_FEE_SPLIT_LOG).unwrap().unwrap();
+    assert_eq!(log.standard, EVENT_STANDARD);
+    assert_eq!(log.version, EVENT_VERSION);
+    assert_eq!(
+        log.event,
+        ConnectorEvent::DepositFeeSplit(vec![DepositFeeSplit {
+            treasury_id: "eth_connector.root".into(),
+            treasury_fee: 225,
+            relayer_id: "root".into(),
+            relayer_fee: 225,
+        }])
+    );
+    assert_eq!(log.to_log(), DEPOSIT_FEE_SPLIT_LOG);
+}
+
+#[test]
+fn test_parse_deposit_submitted() {
+    let log = EventLog::from_log(
+        r#"EVENT_JSON:{"standard":"eth-connector","version":"1.0.0","event":"deposit_submitted","data":[{"trace_id":"5f1e","proof_key":"123","relayer_id":"relayer"}]}"#,
+    )
+    .unwrap()
+    .unwrap();
+    assert_eq!(
+        log.event,
+        ConnectorEvent::DepositSubmitted(vec![DepositSubmitted {
+            trace_id: "5f1e".into(),
+            proof_key: "123".into(),
+            relayer_id: "relayer".into(),
+        }])
+    );
+}
+
+#[test]
+fn test_parse_deposit() {
+    let log = EventLog::from_log(
+        r#"EVENT_JSON:{"standard":"eth-connector","version":"1.0.0","event":"deposit","data":[{"proof_key":"123","new_owner_id":"root","amount":"50450","fee":"450","protocol_fee":"0","relayer_id":"relayer","trace_id":"5f1e","config_hash":"0badc0ffee15600d"}]}"#,
+    )
+    .unwrap()
+    .unwrap();
+    assert_eq!(
+        log.event,
+        ConnectorEvent::Deposit(vec![Deposit {
+            proof_key: "123".into(),
+            new_owner_id: "root".into(),
+            amount: 50450,
+            fee: 450,
+            protocol_fee: 0,
+            relayer_id: "relayer".into(),
+            trace_id: "5f1e".into(),
+            config_hash: "0badc0ffee15600d".into(),
+        }])
+    );
+}
+
+#[test]
+fn test_parse_withdraw() {
+    let log = EventLog::from_log(
+        r#"EVENT_JSON:{"standard":"eth-connector","version":"1.0.0","event":"withdraw","data":[{"owner_id":"root","eth_recipient":"891b2749238b27ff58e951088e55b04de71dc374","amount":"100","fee":"1","config_hash":"0badc0ffee15600d"}]}"#,
+    )
+    .unwrap()
+    .unwrap();
+    assert_eq!(
+        log.event,
+        ConnectorEvent::Withdraw(vec![Withdraw {
+            owner_id: "root".into(),
+            eth_recipient: "891b2749238b27ff58e951088e55b04de71dc374".into(),
+            amount: 100,
+            fee: 1,
+            config_hash: "0badc0ffee15600d".into(),
+        }])
+    );
+}
+
+#[test]
+fn test_parse_deposit_stuck() {
+    let log = EventLog::from_log(
+        r#"EVENT_JSON:{"standard":"eth-connector","version":"1.0.0","event":"deposit_stuck","data":[{"proof_key":"123","relayer_id":"root","trace_id":"5f1e"}]}"#,
+    )
+    .unwrap()
+    .unwrap();
+    assert_eq!(
+        log.event,
+        ConnectorEvent::DepositStuck(vec![DepositStuck {
+            proof_key: "123".into(),
+            relayer_id: "root".into(),
+            trace_id: "5f1e".into(),
+        }])
+    );
+}
+
+#[test]
+fn test_parse_deposit_deferred() {
+    let log = EventLog::from_log(
+        r#"EVENT_JSON:{"standard":"eth-connector","version":"1.0.0","event":"deposit_deferred","data":[{"proof_key":"123","trace_id":"5f1e"}]}"#,
+    )
+    .unwrap()
+    .unwrap();
+    assert_eq!(
+        log.event,
+        ConnectorEvent::DepositDeferred(vec![DepositDeferred {
+            proof_key: "123".into(),
+            trace_id: "5f1e".into(),
+        }])
+    );
+}
+
+#[test]
+fn test_parse_deposit_rolled_back() {
+    let log = EventLog::from_log(
+        r#"EVENT_JSON:{"standard":"eth-connector","version":"1.0.0","event":"deposit_rolled_back","data":[{"proof_key":"123","trace_id":"5f1e","engine_account_id":"aurora"}]}"#,
+    )
+    .unwrap()
+    .unwrap();
+    assert_eq!(
+        log.event,
+        ConnectorEvent::DepositRolledBack(vec![DepositRolledBack {
+            proof_key: "123".into(),
+            trace_id: "5f1e".into(),
+            engine_account_id: "aurora".into(),
+        }])
+    );
+}
+
+#[test]
+fn test_parse_deposit_transfer_fallback() {
+    let log = EventLog::from_log(
+        r#"EVENT_JSON:{"standard":"eth-connector","version":"1.0.0","event":"deposit_transfer_fallback","data":[{"trace_id":"5f1e","receiver_id":"dex.near","amount":"50000"}]}"#,
+    )
+    .unwrap()
+    .unwrap();
+    assert_eq!(
+        log.event,
+        ConnectorEvent::DepositTransferFallback(vec![DepositTransferFallback {
+            trace_id: "5f1e".into(),
+            receiver_id: "dex.near".into(),
+            amount: 50000,
+        }])
+    );
+}
+
+#[test]
+fn test_parse_eth_recipient_block() {
+    let log = EventLog::from_log(
+        r#"EVENT_JSON:{"standard":"eth-connector","version":"1.0.0","event":"eth_recipient_block","data":[{"eth_address":"891b2749238b27ff58e951088e55b04de71dc374","blocked":true}]}"#,
+    )
+    .unwrap()
+    .unwrap();
+    assert_eq!(
+        log.event,
+        ConnectorEvent::EthRecipientBlock(vec![EthRecipientBlock {
+            eth_address: "891b2749238b27ff58e951088e55b04de71dc374".into(),
+            blocked: true,
+        }])
+    );
+}
+
+#[test]
+fn test_parse_prover_disagreement() {
+    let log = EventLog::from_log(
+        r#"EVENT_JSON:{"standard":"eth-connector","version":"1.0.0","event":"prover_disagreement","data":[{"proof_key":"123","shadow_prover_id":"prover.v2","verified":true,"shadow_verified":null}]}"#,
+    )
+    .unwrap()
+    .unwrap();
+    assert_eq!(
+        log.event,
+        ConnectorEvent::ProverDisagreement(vec![ProverDisagreement {
+            proof_key: "123".into(),
+            shadow_prover_id: "prover.v2".into(),
+            verified: true,
+            shadow_verified: None,
+        }])
+    );
+}
+
+#[test]
+fn test_parse_proof_revoked() {
+    let log = EventLog::from_log(
+        r#"EVENT_JSON:{"standard":"eth-connector","version":"1.0.0","event":"proof_revoked","data":[{"proof_key":"123","clawback_account_id":null,"clawed_back":"0"}]}"#,
+    )
+    .unwrap()
+    .unwrap();
+    assert_eq!(
+        log.event,
+        ConnectorEvent::ProofRevoked(vec![ProofRevoked {
+            proof_key: "123".into(),
+            clawback_account_id: None,
+            clawed_back: 0,
+        }])
+    );
+}
+
+#[test]
+fn test_parse_supply_discrepancy() {
+    let log = EventLog::from_log(
+        r#"EVENT_JSON:{"standard":"eth-connector","version":"1.0.0","event":"supply_discrepancy","data":[{"total_minted":"50450","total_burned":"0","total_supply":"50000"}]}"#,
+    )
+    .unwrap()
+    .unwrap();
+    assert_eq!(
+        log.event,
+        ConnectorEvent::SupplyDiscrepancy(vec![SupplyDiscrepancy {
+            total_minted: 50450,
+            total_burned: 0,
+            total_supply: 50000,
+        }])
+    );
+}
+
+#[test]
+fn test_skip_plain_logs() {
+    assert!(EventLog::from_log("Mint success").is_none());
+}
@@ -0,0 +1,91 @@
+#![cfg(feature = "simulation")]
+use eth_connector_events::simulation::*;
+
+const TREASURY: &str = "eth_connector.root";
+
+#[test]
+fn test_deposit_fee_split_rounding() {
+    let mut model = ConnectorModel::new(TREASURY);
+    model.relayer_fee_share_bps = 3_333;
+    let outcome = model.deposit("1", "root", 50450, 450, "relayer").unwrap();
+    assert_eq!(
+        outcome,
+        DepositOutcome {
+            recipient_amount: 50000,
+            treasury_fee: 301,
+            relayer_fee: 149,
+            protocol_fee: 0,
+        }
+    );
+    assert_eq!(model.total_supply, 50450);
+}
+
+#[test]
+fn test_deposit_protocol_fee_accrual() {
+    let mut model = ConnectorModel::new(TREASURY);
+    model.deposit_fee_bps = 30;
+    let outcome = model.deposit("1", "root", 50450, 450, TREASURY).unwrap();
+    assert_eq!(outcome.protocol_fee, 150);
+    assert_eq!(outcome.recipient_amount, 49850);
+    assert_eq!(model.accrued_fees, 150);
+    assert_eq!(model.total_supply, 50300);
+    assert_eq!(model.claim_fees("owner"), 150);
+    assert_eq!(model.accrued_fees, 0);
+    assert_eq!(model.balance_of("owner"), 150);
+    assert_eq!(model.total_supply, 50450);
+}
+
+#[test]
+fn test_deposit_rejections() {
+    let mut model = ConnectorModel::new(TREASURY);
+    assert_eq!(
+        model.deposit("1", "root", 450, 450, TREASURY),
+        Err(ModelError::FeeExceedsAmount)
+    );
+    model.deposit("1", "root", 50450, 450, TREASURY).unwrap();
+    assert_eq!(
+        model.deposit("1", "root", 50450, 450, TREASURY),
+        Err(ModelError::ProofAlreadyUsed)
+    );
+    model.paused_mask = PAUSE_DEPOSIT;
+    assert_eq!(
+        model.deposit("2", "root", 50450, 450, TREASURY),
+        Err(ModelError::DepositPaused)
+    );
+}
+
+#[test]
+fn test_withdraw() {
+    let mut model = ConnectorModel::new(TREASURY);
+    model.deposit("1", "root", 50450, 450, TREASURY).unwrap();
+    let eth_recipient = "891b2749238b27ff58e951088e55b04de71dc374";
+    model.blocked_eth_recipients.insert(eth_recipient.into());
+    assert_eq!(
+        model.withdraw("root", eth_recipient, 100),
+        Err(ModelError::BlockedEthRecipient)
+    );
+    model.blocked_eth_recipients.clear();
+    assert_eq!(
+        model.withdraw("root", eth_recipient, 50001),
+        Err(ModelError::NotEnoughBalance)
+    );
+    assert_eq!(model.withdraw("root", eth_recipient, 100), Ok(100));
+    assert_eq!(model.balance_of("root"), 49900);
+    assert_eq!(model.total_supply, 50350);
+}
+
+#[test]
+fn test_withdraw_fee_and_minimum() {
+    let mut model = ConnectorModel::new(TREASURY);
+    model.deposit("1", "root", 50450, 450, TREASURY).unwrap();
+    model.withdraw_fee_bps = 10;
+    model.min_withdraw_amount = 1000;
+    let eth_recipient = "891b2749238b27ff58e951088e55b04de71dc374";
+    assert_eq!(
+        model.withdraw("root", eth_recipient, 999),
+        Err(ModelError::WithdrawBelowMinimum)
+    );
+    assert_eq!(model.withdraw("root", eth_recipient, 1500), Ok(1498));
+    assert_eq!(model.accrued_fees, 2);
+    assert_eq!(model.balance_of("root"), 48500);
+}
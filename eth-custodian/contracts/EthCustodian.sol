@@ -24,7 +24,12 @@ contract EthCustodian is ProofKeeper, AdminControlled {
 
     event Withdrawn(
         address indexed recipient,
-        uint128 amount
+        uint128 amount,
+        bytes32 memo
+    );
+
+    event WithdrawalInvalidated(
+        bytes32 indexed receiptId
     );
 
     // Function output from burning nETH on Near side.
@@ -32,6 +37,7 @@ contract EthCustodian is ProofKeeper, AdminControlled {
         uint128 amount;
         address recipient;
         address ethCustodian;
+        bytes32 memo;
     }
 
     /// EthCustodian is linked to the EVM on NEAR side.
@@ -122,10 +128,28 @@ contract EthCustodian is ProofKeeper, AdminControlled {
         payable(result.recipient).transfer(result.amount);
         emit Withdrawn(
             result.recipient,
-            result.amount
+            result.amount,
+            result.memo
         );
     }
 
+    /// Invalidates withdrawals that were emitted on the NEAR side but not executed yet,
+    /// e.g. when they were produced by exploited logic.
+    /// `receiptIds` - the NEAR receipt ids of the burn outcomes to reject
+    function adminInvalidateWithdrawals(bytes32[] calldata receiptIds)
+        external
+        onlyAdmin
+    {
+        for (uint i = 0; i < receiptIds.length; i++) {
+            require(
+                !usedEvents_[receiptIds[i]],
+                'The burn event was already withdrawn'
+            );
+            invalidatedEvents_[receiptIds[i]] = true;
+            emit WithdrawalInvalidated(receiptIds[i]);
+        }
+    }
+
     function _decodeBurnResult(bytes memory data)
         internal
         pure
@@ -137,6 +161,7 @@ contract EthCustodian is ProofKeeper, AdminControlled {
         result.recipient = address(uint160(recipient));
         bytes20 ethCustodian = borshData.decodeBytes20();
         result.ethCustodian = address(uint160(ethCustodian));
+        result.memo = borshData.decodeBytes32();
         require(borshData.finished(), "Parse error: EOI expected");
     }
 }
@@ -19,6 +19,9 @@ contract ProofKeeper {
     // OutcomeReciptId -> Used
     mapping(bytes32 => bool) public usedEvents_;
 
+    // OutcomeReciptId -> Invalidated by the admin during incident response
+    mapping(bytes32 => bool) public invalidatedEvents_;
+
     constructor(
         bytes memory nearProofProducerAccount,
         INearProver prover,
@@ -76,6 +79,10 @@ contract ProofKeeper {
             !usedEvents_[receiptId],
             'The burn event cannot be reused'
         );
+        require(
+            !invalidatedEvents_[receiptId],
+            'The burn event was invalidated'
+        );
         usedEvents_[receiptId] = true;
 
         require(
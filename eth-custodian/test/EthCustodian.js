@@ -1,5 +1,6 @@
 const { ethers } = require('hardhat');
 const { expect } = require('chai');
+const bs58 = require('bs58');
 
 const { serialize } = require('rainbow-bridge-lib/rainbow/borsh.js');
 const { borshifyOutcomeProof } = require('rainbow-bridge-lib/rainbow/borshify-proof.js');
@@ -10,6 +11,7 @@ const SCHEMA = {
       ['amount', 'u128'],
       ['recipient', [20]],
       ['ethCustodian', [20]],
+      ['memo', [32]],
     ]
   }
 };
@@ -180,6 +182,7 @@ describe('EthCustodian contract', () => {
                 amount: amount,
                 recipient: ethers.utils.arrayify(user2.address),
                 ethCustodian: ethers.utils.arrayify(ethCustodian.address),
+                memo: ethers.utils.arrayify(ethers.constants.HashZero),
             }).toString('base64');
             // Manually set the incorrect proof producer
             proof.outcome_proof.outcome.executor_id = 'evm2.near';
@@ -199,6 +202,7 @@ describe('EthCustodian contract', () => {
                 recipient: ethers.utils.arrayify(user2.address),
                 // Manually setting the incorrect eth custodian address
                 ethCustodian: ethers.utils.arrayify("0xabcdabcdabcdabcdabcdabcdabcdabcdabcdabcd"),
+                memo: ethers.utils.arrayify(ethers.constants.HashZero),
             }).toString('base64');
 
             await expect(
@@ -211,10 +215,12 @@ describe('EthCustodian contract', () => {
 
         it('Should successfully withdraw and emit the withdrawn event', async () => {
             const amount = 5000; // wei
+            const memo = '0x' + 'ab'.repeat(32);
             proof.outcome_proof.outcome.status.SuccessValue = serialize(SCHEMA, 'Withdrawn', {
                 amount: amount,
                 recipient: ethers.utils.arrayify(user2.address),
                 ethCustodian: ethers.utils.arrayify(ethCustodian.address),
+                memo: ethers.utils.arrayify(memo),
             }).toString('base64');
 
             console.log(`User2 address: ${user2.address}`);
@@ -225,7 +231,7 @@ describe('EthCustodian contract', () => {
             )
                 .to
                 .emit(ethCustodian, 'Withdrawn')
-                .withArgs(user2.address, amount);
+                .withArgs(user2.address, amount, memo);
 
             const balanceAfter = ethers.BigNumber.from(await ethers.provider.getBalance(user2.address));
             const balanceDiff = balanceAfter.sub(balanceBefore);
@@ -241,6 +247,7 @@ describe('EthCustodian contract', () => {
                 amount: amount,
                 recipient: ethers.utils.arrayify(user2.address),
                 ethCustodian: ethers.utils.arrayify(ethCustodian.address),
+                memo: ethers.utils.arrayify(ethers.constants.HashZero),
             }).toString('base64');
 
             // Withdraw for the first time
@@ -255,12 +262,45 @@ describe('EthCustodian contract', () => {
                 .revertedWith('The burn event cannot be reused');
         });
 
+        it('Should revert when the withdrawal was invalidated by the admin', async () => {
+            const amount = 5000; // wei
+            proof.outcome_proof.outcome.status.SuccessValue = serialize(SCHEMA, 'Withdrawn', {
+                amount: amount,
+                recipient: ethers.utils.arrayify(user2.address),
+                ethCustodian: ethers.utils.arrayify(ethCustodian.address),
+                memo: ethers.utils.arrayify(ethers.constants.HashZero),
+            }).toString('base64');
+            const receiptId = ethers.utils.hexlify(bs58.decode(proof.outcome_proof.outcome.receipt_ids[0]));
+
+            await expect(
+                ethCustodian.connect(user2).adminInvalidateWithdrawals([receiptId])
+            )
+                .to
+                .be
+                .reverted;
+
+            await expect(
+                ethCustodian.connect(adminAccount).adminInvalidateWithdrawals([receiptId])
+            )
+                .to
+                .emit(ethCustodian, 'WithdrawalInvalidated')
+                .withArgs(receiptId);
+
+            await expect(
+                ethCustodian.withdraw(borshifyOutcomeProof(proof), 1099)
+            )
+                .to
+                .be
+                .revertedWith('The burn event was invalidated');
+        });
+
         it('Should revert when the proof is coming from the ancient block', async () => {
             const amount = 5000; // wei
             proof.outcome_proof.outcome.status.SuccessValue = serialize(SCHEMA, 'Withdrawn', {
                 amount: amount,
                 recipient: ethers.utils.arrayify(user2.address),
                 ethCustodian: ethers.utils.arrayify(ethCustodian.address),
+                memo: ethers.utils.arrayify(ethers.constants.HashZero),
             }).toString('base64');
 
             const proofBlockHeight = minBlockAcceptanceHeight - 1;
@@ -454,6 +494,7 @@ describe('EthCustodian contract', () => {
                 amount: amountToWithdraw,
                 recipient: ethers.utils.arrayify(user2.address),
                 ethCustodian: ethers.utils.arrayify(ethCustodian.address),
+                memo: ethers.utils.arrayify(ethers.constants.HashZero),
             }).toString('base64');
 
             const recipientBalanceBefore = ethers.BigNumber.from(await ethers.provider.getBalance(user2.address));
@@ -465,7 +506,7 @@ describe('EthCustodian contract', () => {
             )
                 .to
                 .emit(ethCustodian, 'Withdrawn')
-                .withArgs(user2.address, amountToWithdraw);
+                .withArgs(user2.address, amountToWithdraw, ethers.constants.HashZero);
 
             const recipientBalanceAfter = ethers.BigNumber.from(await ethers.provider.getBalance(user2.address));
             const recipientBalanceDiff = recipientBalanceAfter.sub(recipientBalanceBefore);
@@ -521,7 +562,7 @@ describe('EthCustodian contract', () => {
             )
                 .to
                 .emit(ethCustodian, 'Withdrawn')
-                .withArgs(user2.address, amountToWithdraw);
+                .withArgs(user2.address, amountToWithdraw, ethers.constants.HashZero);
 
             const recipientBalanceAfter3 = ethers.BigNumber.from(await ethers.provider.getBalance(user2.address));
             const recipientBalanceDiff3 = recipientBalanceAfter3.sub(recipientBalanceBefore3);
@@ -0,0 +1,39 @@
+use super::*;
+
+pub type PausedMask = u8;
+
+pub const UNPAUSE_ALL: PausedMask = 0;
+pub const PAUSE_DEPOSIT: PausedMask = 1 << 0;
+pub const PAUSE_WITHDRAW: PausedMask = 1 << 1;
+
+/// Admin controls shared with the Aurora eth-connector interface.
+pub trait AdminControlled {
+    /// Return the account administering the contract
+    fn get_owner(&self) -> &AccountId;
+
+    /// Asserts the predecessor is the owner. Panics with "Only the owner can call this method" otherwise.
+    fn assert_owner(&self) {
+        assert!(
+            &sdk::predecessor_account_id() == self.get_owner(),
+            "Only the owner can call this method"
+        );
+    }
+
+    /// Return the current mask of paused flags
+    fn get_paused(&self) -> PausedMask;
+
+    /// Update the mask of paused flags
+    fn set_paused(&mut self, paused: PausedMask);
+
+    /// Return whether any of the given flags is paused
+    fn is_paused(&self, flag: PausedMask) -> bool {
+        (self.get_paused() & flag) != 0
+    }
+
+    /// Asserts the passed paused flag is not set. Panics with `ERR_PAUSED` if paused.
+    fn assert_not_paused(&self, flag: PausedMask) {
+        if self.is_paused(flag) {
+            sdk::panic_utf8(ConnectorError::Paused.as_ref());
+        }
+    }
+}
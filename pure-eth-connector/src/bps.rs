@@ -0,0 +1,38 @@
+//! Basis point math of the connector fees.
+//!
+//! The `simulation` model of `eth-connector-events` includes this file with `#[path]`, so
+//! both use the same rounding. It must only depend on `crate::Balance`, defined by both.
+use crate::Balance;
+
+/// Denominator of basis point ratios.
+pub const BPS_DENOMINATOR: u16 = 10_000;
+
+// Rounding policy for all fee math of the connector: fees charged to users round up,
+// payouts out of collected fees round down. The contract never pays out more than it
+// collected, and integrators reproduce amounts to the yocto with the same two helpers.
+
+/// Fee of `bps` basis points charged on `amount`, rounded up.
+pub fn bps_fee_ceil(amount: Balance, bps: u16) -> Balance {
+    let (floor, remainder) = bps_mul_div(amount, bps);
+    if remainder > 0 {
+        floor + 1
+    } else {
+        floor
+    }
+}
+
+/// Payout of `bps` basis points out of `amount`, rounded down.
+pub fn bps_payout_floor(amount: Balance, bps: u16) -> Balance {
+    bps_mul_div(amount, bps).0
+}
+
+/// `amount * bps / BPS_DENOMINATOR` without overflowing, with the remainder of the division.
+fn bps_mul_div(amount: Balance, bps: u16) -> (Balance, Balance) {
+    let bps = Balance::from(bps);
+    let denominator = Balance::from(BPS_DENOMINATOR);
+    let low = amount % denominator * bps;
+    (
+        amount / denominator * bps + low / denominator,
+        low % denominator,
+    )
+}
@@ -1,9 +1,121 @@
 use super::*;
+use core::convert::TryFrom;
 
 pub const CONTRACT_NAME_KEY: &str = "EthConnector";
 pub const CONTRACT_FT_KEY: &str = "EthConnector.FungibleToken";
+pub const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+/// Source revision the wasm was built from, set by the reproducible build.
+pub const SOURCE_COMMIT: Option<&str> = option_env!("SOURCE_COMMIT");
+/// Version of the Borsh layout of the stored contract state.
+pub const STATE_VERSION: u32 = 2;
+/// Layout version of the stored state, missing for states written before it was tracked.
+pub const STATE_VERSION_KEY: &str = "EthConnector.StateVersion";
+/// Highest Ethereum block of proofs which may be recorded under their legacy key,
+/// missing for states written before compact keys.
+const LEGACY_PROOF_KEY_HEIGHT_KEY: &str = "EthConnector.LegacyProofKeyHeight";
+/// Set once `migrate` imported the state of another deployment.
+const MIGRATED_KEY: &str = "EthConnector.Migrated";
 const GAS_FOR_FINISH_DEPOSIT: Gas = 10_000_000_000_000;
 const GAS_FOR_VERIFY_LOG_ENTRY: Gas = 40_000_000_000_000;
+const GAS_FOR_LAST_BLOCK_NUMBER: Gas = 5_000_000_000_000;
+const GAS_FOR_FINISH_REGISTER_ALIAS: Gas = 5_000_000_000_000;
+const GAS_FOR_FT_ON_DEPOSIT: Gas = 10_000_000_000_000;
+const GAS_FOR_FINISH_ENGINE_DEPOSIT: Gas = 10_000_000_000_000;
+const GAS_FOR_REGISTER_BRIDGE: Gas = 10_000_000_000_000;
+const GAS_FOR_MIGRATE_STATE: Gas = 50_000_000_000_000;
+/// Left for minting a verified deposit, below it the deposit is deferred.
+const GAS_FOR_MINT_DEPOSIT: Gas = 5_000_000_000_000;
+/// Minimum time between two `register_self` refreshes, in nanoseconds: 1 day.
+const REGISTRY_REFRESH_INTERVAL: u64 = 86_400_000_000_000;
+/// Upper bound of the storage taken by a used proof record until the first one is
+/// measured: the 40 bytes of record overhead, a key of 24 + 64 bytes and two
+/// `Option<AccountId>` of at most 69 bytes each, plus its pruning index entry of 40
+/// bytes of overhead, a key of at most 30 + 20 bytes and a value of 76 bytes.
+const USED_PROOF_STORAGE_BYTES: u64 = 432;
+const MAX_PROOF_KEY_LEN: u64 = 64;
+const MAX_INDEX_LEN: u64 = 20;
+const MAX_ACCOUNT_ID_LEN: u64 = 64;
+const MIN_ACCOUNT_ID_LEN: u64 = 2;
+/// Maximum number of holders returned by one `get_accounts` call.
+const MAX_ACCOUNTS_PAGE: u64 = 100;
+/// Number of stuck deposit submissions kept for operators.
+const MAX_STUCK_SUBMISSIONS: usize = 100;
+/// Number of most recent deposits and withdrawals kept per account.
+const MAX_ACCOUNT_HISTORY: usize = 32;
+/// Recipients starting with this prefix are resolved through the alias registry.
+pub const ALIAS_PREFIX: &str = "@";
+const MIN_ALIAS_LEN: usize = 2;
+const MAX_ALIAS_LEN: usize = 64;
+
+/// Families of per-item storage keys, laid out as `EthConnector.<prefix>.<parts>`.
+/// Prefixes are part of the stored state: never rename or reuse one, add a variant
+/// for each new collection instead.
+#[derive(Clone, Copy)]
+pub enum KeyPrefix {
+    UsedEvent,
+    RevokedProof,
+    DepositTrace,
+    DepositProof,
+    History,
+    Stuck,
+    BlockedEthRecipient,
+    WithdrawAllowance,
+    Alias,
+    Holder,
+    HolderIndex,
+    RefusalPolicy,
+    Timelock,
+    SchemaLog,
+    CustodianHistory,
+    DeferredDeposit,
+    Webhook,
+    BlockConfirmations,
+    UsedEventIndex,
+    WithdrawIntent,
+    /// Reserved for upcoming collections.
+    #[allow(dead_code)]
+    Registry,
+    #[allow(dead_code)]
+    Claims,
+    #[allow(dead_code)]
+    Queues,
+}
+
+impl KeyPrefix {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::UsedEvent => "used-event",
+            Self::RevokedProof => "revoked-proof",
+            Self::DepositTrace => "deposit-trace",
+            Self::DepositProof => "deposit-proof",
+            Self::History => "history",
+            Self::Stuck => "stuck",
+            Self::BlockedEthRecipient => "blocked-eth-recipient",
+            Self::WithdrawAllowance => "withdraw-allowance",
+            Self::Alias => "alias",
+            Self::Holder => "holder",
+            Self::HolderIndex => "holder-index",
+            Self::RefusalPolicy => "refusal-policy",
+            Self::Timelock => "timelock",
+            Self::SchemaLog => "schema-log",
+            Self::CustodianHistory => "custodian-history",
+            Self::DeferredDeposit => "deferred-deposit",
+            Self::Webhook => "webhook",
+            Self::BlockConfirmations => "block-confirmations",
+            Self::UsedEventIndex => "used-event-index",
+            Self::WithdrawIntent => "withdraw-intent",
+            Self::Registry => "registry",
+            Self::Claims => "claims",
+            Self::Queues => "queues",
+        }
+    }
+}
+
+pub fn storage_key(prefix: KeyPrefix, parts: &[&str]) -> String {
+    let mut key = vec![CONTRACT_NAME_KEY, prefix.as_str()];
+    key.extend_from_slice(parts);
+    key.join(".")
+}
 
 pub struct EthConnectorContract {
     contract: EthConnector,
@@ -18,8 +130,11 @@ impl Default for EthConnectorContract {
 
 impl EthConnectorContract {
     pub fn new() -> Self {
+        let mut contract: EthConnector = sdk::get_contract_data(CONTRACT_NAME_KEY);
+        // Due scheduled changes are applied on load and persisted by the next state write
+        contract.apply_scheduled_changes(sdk::block_timestamp());
         Self {
-            contract: sdk::get_contract_data(CONTRACT_NAME_KEY),
+            contract,
             ft: sdk::get_contract_data(CONTRACT_FT_KEY),
         }
     }
@@ -34,27 +149,280 @@ impl EthConnectorContract {
         sdk::log("[init contract]".into());
         let args: InitCallArgs =
             InitCallArgs::from(parse_json(&sdk::read_input()).expect(FAILED_PARSE));
+        assert!(
+            Self::is_valid_account_id(&args.prover_account),
+            "Invalid prover account"
+        );
+        if let Some(owner_id) = &args.owner_id {
+            assert!(Self::is_valid_account_id(owner_id), "Invalid owner account");
+        }
+        let eth_custodian_address = EthAddress::try_from(args.eth_custodian_address.as_str())
+            .unwrap_or_else(|err| sdk::panic_utf8(err.as_ref()));
+        assert!(
+            eth_custodian_address != EthAddress::default(),
+            "Custodian address is zero"
+        );
+        let relayer_fee_share_bps = args.relayer_fee_share_bps.unwrap_or(0);
+        assert!(
+            relayer_fee_share_bps <= BPS_DENOMINATOR,
+            "Relayer fee share exceeds 100%"
+        );
+        let deposit_fee_bps = args.deposit_fee_bps.unwrap_or(0);
+        assert!(
+            deposit_fee_bps <= MAX_DEPOSIT_FEE_BPS,
+            "Deposit fee exceeds the maximum"
+        );
+        let withdraw_fee_bps = args.withdraw_fee_bps.unwrap_or(0);
+        assert!(
+            withdraw_fee_bps <= MAX_WITHDRAW_FEE_BPS,
+            "Withdraw fee exceeds the maximum"
+        );
         let owner_id = sdk::current_account_id();
         let mut ft = FungibleToken::new();
         ft.internal_register_account(owner_id);
         let contract_data = EthConnector {
+            // The contract account administers itself unless an owner is given
+            owner_id: args.owner_id.unwrap_or_else(sdk::current_account_id),
+            proposed_owner_id: None,
             prover_account: args.prover_account,
-            eth_custodian_address: validate_eth_address(args.eth_custodian_address),
+            eth_custodian_address,
+            deposited_event_layout: EventLayout::default(),
+            paused_mask: UNPAUSE_ALL,
+            engine_account: None,
+            relayer_fee_share_bps,
+            scheduled_changes: Vec::new(),
+            maintenance_banner: None,
+            last_eth_block_height: 0,
+            expected_code_hash: None,
+            event_topics: EventTopics::default(),
+            total_minted: 0,
+            total_burned: 0,
+            deposit_fee_bps,
+            accrued_fees: 0,
+            withdraw_fee_bps,
+            min_withdraw_amount: args.min_withdraw_amount.unwrap_or(0),
+            frozen_mask: 0,
+            shadow_prover: None,
+            used_proof_storage_bytes: None,
+            registry: None,
         };
         Self {
             contract: contract_data,
             ft,
         }
         .save_contract();
+        sdk::save_contract(STATE_VERSION_KEY, &STATE_VERSION);
+        sdk::save_contract(LEGACY_PROOF_KEY_HEIGHT_KEY, &0u64);
+        Self::push_schema_log();
+    }
+
+    /// Deploy the wasm passed as raw input to this account and run `migrate_state` with
+    /// the new code. When a code hash expectation is set, the code must match it.
+    pub fn upgrade(&self) {
+        self.assert_owner();
+        let code = sdk::read_input();
+        let code_hash = sdk::sha256(&code);
+        if let Some(expected_code_hash) = &self.contract.expected_code_hash {
+            assert!(
+                code_hash[..] == expected_code_hash[..],
+                "Code doesn't match the code hash expectation"
+            );
+        }
+        let mut timelock = self.timelock();
+        if timelock.delay > 0 {
+            // Consumes the unlocked `Upgrade` action staged for this code
+            let now = sdk::block_timestamp();
+            let index = timelock
+                .actions
+                .iter()
+                .position(|staged| {
+                    staged.unlock_at <= now
+                        && matches!(&staged.action, AdminAction::Upgrade(hash) if hash[..] == code_hash[..])
+                })
+                .unwrap_or_else(|| sdk::panic_utf8(b"Upgrade is not staged or still locked"));
+            timelock.actions.remove(index);
+            self.save_timelock(&timelock);
+        }
+        let promise0 = sdk::promise_batch_create(sdk::current_account_id());
+        sdk::promise_batch_action_deploy_contract(promise0, &code);
+        // A failed migration reverts the deployment with it, as both are in one receipt
+        sdk::promise_batch_action_function_call(
+            promise0,
+            b"migrate_state",
+            &[],
+            sdk::NO_DEPOSIT,
+            GAS_FOR_MIGRATE_STATE,
+        );
+    }
+
+    /// Bring the stored state to the layout of this code, called by `upgrade` right
+    /// after the deployment. Runs before the state is loaded, since an older layout
+    /// can't be read as the current `EthConnector`.
+    pub fn migrate_state() {
+        sdk::assert_private_call();
+        let state_version = sdk::read_storage(STATE_VERSION_KEY.as_bytes())
+            .map(|data| u32::try_from_slice(&data[..]).unwrap())
+            .unwrap_or(1);
+        assert!(
+            state_version <= STATE_VERSION,
+            "Stored state is newer than the contract code"
+        );
+        // A layout change bumps STATE_VERSION and converts the state of the previous
+        // version here, one version after another
+        if state_version < 2 {
+            Self::migrate_state_v1();
+        }
+        sdk::save_contract(STATE_VERSION_KEY, &STATE_VERSION);
+        // Used proof records may have grown since they were measured, measure again
+        let mut contract: EthConnector = sdk::get_contract_data(CONTRACT_NAME_KEY);
+        contract.used_proof_storage_bytes = None;
+        sdk::save_contract(CONTRACT_NAME_KEY, &contract);
+        // Proofs accepted so far were recorded under legacy keys. Their heights aren't
+        // known, as `last_eth_block_height` wasn't tracked from the start, so always check
+        if !sdk::storage_has_key(LEGACY_PROOF_KEY_HEIGHT_KEY) {
+            sdk::save_contract(LEGACY_PROOF_KEY_HEIGHT_KEY, &u64::MAX);
+        }
+        Self::push_schema_log();
+    }
+
+    /// Convert the state layout of the initial release, which only had the prover and
+    /// custodian and no holders index. The index is filled by `backfill_holders`.
+    fn migrate_state_v1() {
+        let contract: EthConnectorV1 = sdk::get_contract_data(CONTRACT_NAME_KEY);
+        let ft: FungibleTokenV1 = sdk::get_contract_data(CONTRACT_FT_KEY);
+        let contract = EthConnector {
+            owner_id: sdk::current_account_id(),
+            proposed_owner_id: None,
+            prover_account: contract.prover_account,
+            eth_custodian_address: contract.eth_custodian_address,
+            deposited_event_layout: EventLayout::default(),
+            paused_mask: UNPAUSE_ALL,
+            engine_account: None,
+            relayer_fee_share_bps: 0,
+            scheduled_changes: Vec::new(),
+            maintenance_banner: None,
+            last_eth_block_height: 0,
+            expected_code_hash: None,
+            event_topics: EventTopics::default(),
+            // Withdrawals burned from the supply, so it is all that is known to be minted
+            total_minted: ft.total_supply,
+            total_burned: 0,
+            deposit_fee_bps: 0,
+            accrued_fees: 0,
+            withdraw_fee_bps: 0,
+            min_withdraw_amount: 0,
+            frozen_mask: 0,
+            shadow_prover: None,
+            used_proof_storage_bytes: None,
+            registry: None,
+        };
+        let ft = FungibleToken {
+            total_supply: ft.total_supply,
+            account_storage_usage: ft.account_storage_usage,
+            accounts_counter: 0,
+        };
+        sdk::save_contract(CONTRACT_NAME_KEY, &contract);
+        sdk::save_contract(CONTRACT_FT_KEY, &ft);
+    }
+
+    /// Add registered accounts missing from the holders index, as those registered before
+    /// state version 2. Accounts already indexed are skipped, so batches can overlap.
+    pub fn backfill_holders(&mut self) {
+        self.assert_owner();
+        let args =
+            BackfillHoldersCallArgs::from(parse_json(&sdk::read_input()).expect(FAILED_PARSE));
+        for account_id in args.account_ids {
+            self.ft.backfill_holder(account_id);
+        }
+        self.save_contract();
+    }
+
+    /// Versions of the code and state layout since initialization, oldest first, with
+    /// the block from which each ran. Deployments older than the log start at a migration.
+    pub fn get_schema_log(&self) {
+        let res = Self::schema_log().try_to_vec().unwrap();
+        sdk::value_return(&res[..]);
     }
 
     pub fn deposit(&self) {
+        self.internal_deposit(&mut Runtime, false)
+    }
+
+    /// Same as `deposit`, but succeeds with `DepositStatus::AlreadyUsed` instead of failing
+    /// when the proof was already consumed, e.g. by a competing relayer.
+    pub fn deposit_if_unused(&self) {
+        self.internal_deposit(&mut Runtime, true)
+    }
+
+    /// `deposit_if_unused` finished within the same call, with `verified` served as the
+    /// prover result and a missing `verified` standing for a failed prover call.
+    /// Without a shadow prover or block confirmations, which expect more results.
+    #[cfg(feature = "integration-test")]
+    pub fn mock_deposit(&mut self) {
+        let verified = parse_json(&sdk::read_input())
+            .expect(FAILED_PARSE)
+            .bool("verified")
+            .ok();
+        let mut handler = MockPromiseHandler::default();
+        self.internal_deposit(&mut handler, true);
+        let callback = match handler.find_call(b"finish_deposit") {
+            Some(callback) => callback.clone(),
+            None => {
+                if let Some(value) = handler.returned_value {
+                    sdk::value_return(&value[..]);
+                }
+                return;
+            }
+        };
+        let data = FinishDepositCallArgs::try_from_slice(&callback.args[..]).unwrap();
+        let prover_result = match verified {
+            Some(verified) => PromiseResult::Successful(verified.try_to_vec().unwrap()),
+            None => PromiseResult::Failed,
+        };
+        let mut handler = MockPromiseHandler {
+            results: vec![prover_result],
+            ..Default::default()
+        };
+        self.internal_finish_deposit(&mut handler, data);
+        if let Some(value) = handler.returned_value {
+            sdk::value_return(&value[..]);
+        }
+    }
+
+    fn internal_deposit<P: PromiseHandler>(&self, handler: &mut P, skip_used: bool) {
+        self.assert_not_in_maintenance();
         #[cfg(feature = "log")]
         sdk::log("[Deposit tokens]".into());
-        use core::ops::Sub;
+        if self.is_paused(PAUSE_DEPOSIT) {
+            self.refuse(REFUSE_PAUSED, ConnectorError::Paused.as_ref());
+            return;
+        }
 
-        let proof: Proof = Proof::from(parse_json(&sdk::read_input()).unwrap());
-        let event = EthDepositedEvent::from_log_entry_data(&proof.log_entry_data);
+        let input = parse_json(&sdk::read_input())
+            .unwrap_or_else(|| sdk::panic_utf8(ConnectorError::InvalidInput.as_ref()));
+        // Optional, cross-checks the receipt and is kept in the deposit receipt
+        let eth_tx_hash = input.string("eth_tx_hash").ok().map(parse_eth_tx_hash);
+        let versioned_proof = VersionedProof::from(input);
+        let proof = versioned_proof.proof();
+        proof
+            .validate()
+            .unwrap_or_else(|err| sdk::panic_utf8(err.as_ref()));
+        if let Some(eth_tx_hash) = &eth_tx_hash {
+            proof
+                .validate_receipt()
+                .unwrap_or_else(|err| sdk::panic_utf8(err.as_ref()));
+        }
+        if skip_used && self.check_used_proof(proof) {
+            // Not a failure, so the runtime keeps the attachment unless it is sent back
+            Self::refund_attached_deposit();
+            handler.value_return(&DepositStatus::AlreadyUsed.try_to_vec().unwrap()[..]);
+            return;
+        }
+        let event = EthDepositedEvent::from_log_entry_data(
+            &proof.log_entry_data,
+            &self.contract.deposited_event_layout,
+            &self.contract.event_topics.deposited,
+        );
         #[cfg(feature = "log")]
         sdk::log(format!(
             "Deposit started: from {} ETH to {} NEAR with amount: {:?} and fee {:?}",
@@ -64,17 +432,13 @@ impl EthConnectorContract {
             event.fee.as_u128()
         ));
 
-        assert_eq!(
-            event.eth_custodian_address,
-            self.contract.eth_custodian_address,
-            "Event's address {} does not match custodian address {}",
-            hex::encode(&event.eth_custodian_address),
-            hex::encode(&self.contract.eth_custodian_address),
-        );
-        assert!(
-            event.amount.sub(event.fee).as_u128() > 0,
-            "Not enough balance for deposit fee"
-        );
+        self.validate_deposit_event(&event, proof)
+            .unwrap_or_else(|err| sdk::panic_utf8(err.as_ref()));
+        // Kept even if the prover rejects the proof, as for any other failed submission
+        let storage_deposit = self.used_proof_storage_deposit();
+        Self::charge_storage_deposit(storage_deposit);
+        let trace_id = Self::trace_id(proof);
+        events::emit_deposit_submitted(&trace_id, &proof.get_key(), &sdk::predecessor_account_id());
         let account_id = sdk::current_account_id();
         let proof_1 = proof.try_to_vec().unwrap();
         #[cfg(feature = "log")]
@@ -82,74 +446,532 @@ impl EthConnectorContract {
             "Deposit verify_log_entry for prover: {}",
             self.contract.prover_account,
         ));
-        let promise0 = sdk::promise_create(
-            self.contract.prover_account.clone(),
-            b"verify_log_entry",
-            &proof_1[..],
-            sdk::NO_DEPOSIT,
-            GAS_FOR_VERIFY_LOG_ENTRY,
-        );
+        let mut promise0 = handler.promise_create_call(&PromiseCreateArgs {
+            target_account_id: self.contract.prover_account.clone(),
+            method: b"verify_log_entry",
+            args: proof_1.clone(),
+            attached_balance: sdk::NO_DEPOSIT,
+            attached_gas: GAS_FOR_VERIFY_LOG_ENTRY,
+        });
+        // The shadow prover result only gets compared, the primary one gates the deposit
+        if let Some(shadow_prover) = &self.contract.shadow_prover {
+            let shadow_promise = handler.promise_create_call(&PromiseCreateArgs {
+                target_account_id: shadow_prover.account_id.clone(),
+                method: b"verify_log_entry",
+                args: proof_1,
+                attached_balance: sdk::NO_DEPOSIT,
+                attached_gas: GAS_FOR_VERIFY_LOG_ENTRY,
+            });
+            promise0 = handler.promise_and(&[promise0, shadow_promise]);
+        }
+        // The light client height comes last, checked against the proof block by `finish_deposit`
+        let block_confirmations = self.block_confirmations();
+        if let Some(block_confirmations) = &block_confirmations {
+            let client_promise = handler.promise_create_call(&PromiseCreateArgs {
+                target_account_id: block_confirmations.light_client_account.clone(),
+                method: b"last_block_number",
+                args: Vec::new(),
+                attached_balance: sdk::NO_DEPOSIT,
+                attached_gas: GAS_FOR_LAST_BLOCK_NUMBER,
+            });
+            promise0 = handler.promise_and(&[promise0, client_promise]);
+        }
+        let new_owner_id = self.resolve_recipient(event.recipient, proof);
+        let gas_for_finish_deposit = if self.is_engine_deposit(&new_owner_id, &event.message) {
+            GAS_FOR_FINISH_DEPOSIT + GAS_FOR_FT_ON_DEPOSIT + GAS_FOR_FINISH_ENGINE_DEPOSIT
+        } else if event.message.is_some() {
+            GAS_FOR_FINISH_DEPOSIT + GAS_FOR_FT_TRANSFER_CALL
+        } else {
+            GAS_FOR_FINISH_DEPOSIT
+        };
         let data = FinishDepositCallArgs {
-            new_owner_id: event.recipient,
+            new_owner_id,
             amount: event.amount.as_u128(),
             fee: event.fee.as_u128(),
-            proof,
+            proof: versioned_proof,
+            msg: event.message,
+            relayer_id: sdk::predecessor_account_id(),
+            prover_account: self.contract.prover_account.clone(),
+            min_block_confirmations: block_confirmations.map_or(0, |block_confirmations| {
+                block_confirmations.min_block_confirmations
+            }),
+            eth_tx_hash,
+            skip_used,
+            sender: event.sender,
+            trace_id,
+            storage_deposit,
         }
         .try_to_vec()
         .unwrap();
 
-        let promise1 = sdk::promise_then(
+        let promise1 = handler.promise_attach_callback(
             promise0,
-            account_id,
-            b"finish_deposit",
-            &data[..],
-            sdk::NO_DEPOSIT,
-            GAS_FOR_FINISH_DEPOSIT,
+            &PromiseCreateArgs {
+                target_account_id: account_id,
+                method: b"finish_deposit",
+                args: data,
+                attached_balance: sdk::NO_DEPOSIT,
+                attached_gas: gas_for_finish_deposit,
+            },
         );
-        sdk::promise_return(promise1);
+        handler.promise_return(promise1);
     }
 
     pub fn finish_deposit(&mut self) {
+        self.assert_not_in_maintenance();
         sdk::assert_private_call();
+        // Deposits paused while the prover call was in flight are not minted,
+        // their proofs stay unused and can be submitted again once unpaused
+        self.assert_not_paused(PAUSE_DEPOSIT);
         let data: FinishDepositCallArgs =
             FinishDepositCallArgs::try_from_slice(&sdk::read_input()).unwrap();
+        self.internal_finish_deposit(&mut Runtime, data)
+    }
+
+    fn internal_finish_deposit<P: PromiseHandler>(
+        &mut self,
+        handler: &mut P,
+        data: FinishDepositCallArgs,
+    ) {
         #[cfg(feature = "log")]
         sdk::log(format!("Finish deposit amount: {}", data.amount));
-        assert_eq!(sdk::promise_results_count(), 1);
-        let data0: Vec<u8> = match sdk::promise_result(0) {
+        let client_results = if data.min_block_confirmations > 0 {
+            1
+        } else {
+            0
+        };
+        let prover_results = handler
+            .promise_results_count()
+            .saturating_sub(client_results);
+        // A second result comes from the shadow prover
+        let shadow_result = match prover_results {
+            1 => None,
+            2 => Some(handler.promise_result(1)),
+            _ => sdk::panic_utf8(ConnectorError::UnexpectedPromiseResults.as_ref()),
+        };
+        let data0: Vec<u8> = match handler.promise_result(0) {
             PromiseResult::Successful(x) => x,
-            _ => panic!("Promise with index 0 failed"),
+            // The prover ran out of gas or failed: keep the submission for a retry
+            // instead of failing opaquely. The proof is not marked as used.
+            _ => {
+                self.record_stuck_submission(&data);
+                if data.skip_used {
+                    handler.value_return(&DepositStatus::Retryable.try_to_vec().unwrap()[..]);
+                }
+                return;
+            }
         };
         #[cfg(feature = "log")]
         sdk::log("Check verification_success".into());
         let verification_success: bool = bool::try_from_slice(&data0).unwrap();
-        assert!(verification_success, "Failed to verify the proof");
-        self.record_proof(data.proof.get_key());
+        if let Some(shadow_result) = shadow_result {
+            self.record_shadow_verification(&data, verification_success, shadow_result);
+        }
+        if !verification_success {
+            sdk::panic_utf8(ConnectorError::ProofVerificationFailed.as_ref());
+        }
+        if data.min_block_confirmations > 0
+            && !Self::is_confirmed(handler.promise_result(prover_results), &data)
+        {
+            // Not the relayer's fault, so the retry doesn't cost it another storage deposit
+            handler.promise_transfer(data.relayer_id.clone(), data.storage_deposit);
+            if data.skip_used {
+                handler.value_return(&DepositStatus::NotConfirmed.try_to_vec().unwrap()[..]);
+            }
+            return;
+        }
+        let proof_key = data.proof.proof().get_key();
+        // A competing relayer may have consumed the proof after `deposit_if_unused` checked it
+        if data.skip_used && self.check_used_proof(data.proof.proof()) {
+            handler.value_return(&DepositStatus::AlreadyUsed.try_to_vec().unwrap()[..]);
+            return;
+        }
+        if self.check_used_legacy_key(data.proof.proof()) {
+            sdk::panic_utf8(ConnectorError::ProofAlreadyUsed.as_ref());
+        }
+        // The record of a replayed proof may have been pruned while the prover call was in flight
+        if self.is_pruned_block(data.proof.proof()) {
+            sdk::panic_utf8(ConnectorError::ProofTooOld.as_ref());
+        }
+        let used_event_index = self.record_proof(
+            proof_key.clone(),
+            data.proof.proof().block_number().unwrap(),
+            &data.relayer_id,
+            &data.prover_account,
+        );
+        self.remove_stuck_submission(&proof_key);
+        // The proof is spent, so a deposit left without gas for minting must stay completable
+        if sdk::prepaid_gas() - sdk::used_gas() < self.gas_to_mint_deposit(&data) {
+            sdk::save_contract(
+                self.deferred_deposit_key(&proof_key).as_str(),
+                &(data, used_event_index),
+            );
+            events::emit_deposit_deferred(&proof_key, &data.trace_id);
+            self.save_contract();
+            if data.skip_used {
+                handler.value_return(&DepositStatus::Deferred.try_to_vec().unwrap()[..]);
+            }
+            return;
+        }
+        self.mint_verified_deposit(handler, data, proof_key, used_event_index);
+    }
+
+    /// Whether the light client has `min_block_confirmations` blocks on top of the proof
+    /// block. Unconfirmed proofs are not marked as used and can be submitted again later.
+    fn is_confirmed(result: PromiseResult, data: &FinishDepositCallArgs) -> bool {
+        let last_block_number = match result {
+            PromiseResult::Successful(x) => u64::try_from_slice(&x).ok(),
+            _ => None,
+        };
+        let block_number = data.proof.proof().block_number().unwrap();
+        last_block_number.map_or(false, |last_block_number| {
+            last_block_number >= block_number.saturating_add(data.min_block_confirmations)
+        })
+    }
+
+    /// Mint a deferred deposit, callable by anyone with enough gas.
+    pub fn complete_deposit(&mut self) {
+        self.assert_not_in_maintenance();
+        self.assert_not_paused(PAUSE_DEPOSIT);
+        let args = ProofKeyCallArgs::from(parse_json(&sdk::read_input()).expect(FAILED_PARSE));
+        let key = self.deferred_deposit_key(&args.proof_key);
+        let (data, used_event_index) = sdk::read_storage(key.as_bytes())
+            .map(|data| <(FinishDepositCallArgs, u64)>::try_from_slice(&data[..]).unwrap())
+            .unwrap_or_else(|| sdk::panic_utf8(b"No deferred deposit for the proof"));
+        sdk::remove_storage(key.as_bytes());
+        self.mint_verified_deposit(&mut Runtime, data, args.proof_key, used_event_index);
+    }
+
+    /// Gas `finish_deposit` needs after verification: minting, and the engine or
+    /// `ft_transfer_call` forwarding of deposits with a message.
+    fn gas_to_mint_deposit(&self, data: &FinishDepositCallArgs) -> Gas {
+        if self.is_engine_deposit(&data.new_owner_id, &data.msg) {
+            GAS_FOR_MINT_DEPOSIT + GAS_FOR_FT_ON_DEPOSIT + GAS_FOR_FINISH_ENGINE_DEPOSIT
+        } else if data.msg.is_some() {
+            GAS_FOR_MINT_DEPOSIT + GAS_FOR_FT_TRANSFER_CALL
+        } else {
+            GAS_FOR_MINT_DEPOSIT
+        }
+    }
+
+    fn mint_verified_deposit<P: PromiseHandler>(
+        &mut self,
+        handler: &mut P,
+        data: FinishDepositCallArgs,
+        proof_key: String,
+        used_event_index: u64,
+    ) {
+        let eth_block_height = self.record_eth_block_height(data.proof.proof());
+        #[cfg(feature = "log")]
+        if let Some(msg) = &data.msg {
+            sdk::log(format!(
+                "Deposit message for {}: {}",
+                data.new_owner_id, msg
+            ));
+        }
 
-        // Mint tokens to recipient minus fee
-        self.mint(data.new_owner_id, data.amount - data.fee);
-        // Mint fee for Predecessor
-        self.mint(sdk::predecessor_account_id(), data.fee);
+        let is_engine_deposit = self.is_engine_deposit(&data.new_owner_id, &data.msg);
+        // The protocol fee is charged on top of the relayer fee and accrues until claimed
+        let protocol_fee = bps_fee_ceil(data.amount - data.fee, self.contract.deposit_fee_bps);
+        let recipient_amount = data.amount - data.fee - protocol_fee;
+        self.contract.accrued_fees += protocol_fee;
+        if data.msg.is_some() && !is_engine_deposit {
+            // Recipient fields of the form `account.near:msg` are minted to the connector
+            // and forwarded with `ft_transfer_call`, unused tokens are refunded to it.
+            // NEAR can't tell whether a contract implements `ft_on_transfer` without
+            // calling it, so a failed call is handled in `finish_deposit_transfer`.
+            let current_account_id = sdk::current_account_id();
+            self.mint(current_account_id.clone(), recipient_amount);
+            if self.ft.accounts_get(data.new_owner_id.clone()).is_none() {
+                self.ft.internal_register_account(data.new_owner_id.clone());
+            }
+            self.ft.internal_transfer(
+                &current_account_id,
+                &data.new_owner_id,
+                recipient_amount,
+                None,
+            );
+        } else {
+            // Mint tokens to recipient minus fees
+            self.mint(data.new_owner_id.clone(), recipient_amount);
+        }
+        // Split fee between the treasury (the connector) and the relayer
+        let treasury_id = sdk::current_account_id();
+        let relayer_fee = self.relayer_fee(&treasury_id, &data.relayer_id, data.fee);
+        let treasury_fee = data.fee - relayer_fee;
+        self.mint(treasury_id.clone(), treasury_fee);
+        if relayer_fee > 0 {
+            self.mint(data.relayer_id.clone(), relayer_fee);
+        }
+        events::emit_deposit(
+            &proof_key,
+            &data.new_owner_id,
+            data.amount,
+            data.fee,
+            protocol_fee,
+            &data.relayer_id,
+            &data.trace_id,
+            &self.contract.config_hash(),
+        );
+        events::emit_deposit_fee_split(&treasury_id, treasury_fee, &data.relayer_id, relayer_fee);
+        let minted_at = sdk::block_timestamp();
+        self.push_history(
+            &data.new_owner_id,
+            HistoryEntry {
+                kind: HistoryKind::Deposit,
+                amount: data.amount,
+                fee: data.fee,
+                eth_address: data.sender.clone(),
+                timestamp: minted_at,
+            },
+        );
+        let receipt = DepositReceipt {
+            proof_key: proof_key.clone(),
+            new_owner_id: data.new_owner_id.clone(),
+            amount: data.amount,
+            fee: data.fee,
+            relayer_id: data.relayer_id.clone(),
+            relayer_fee,
+            protocol_fee,
+            eth_block_height,
+            trace_id: data.trace_id.clone(),
+            prover_gas: GAS_FOR_VERIFY_LOG_ENTRY,
+            finish_deposit_gas: sdk::prepaid_gas(),
+            eth_tx_hash: data.eth_tx_hash,
+        };
+        sdk::save_contract(self.deposit_receipt_key(&data.trace_id).as_str(), &receipt);
+        sdk::save_contract(self.deposit_proof_key(&proof_key).as_str(), &data.trace_id);
         // Save new contract data
         self.save_contract();
+
+        if is_engine_deposit {
+            // The engine holds the minted tokens and credits the recipient from the message
+            let args = FtOnDepositArgs {
+                recipient: data.msg.unwrap(),
+                amount: recipient_amount,
+            }
+            .try_to_vec()
+            .unwrap();
+            let promise0 = handler.promise_create_call(&PromiseCreateArgs {
+                target_account_id: data.new_owner_id.clone(),
+                method: b"ft_on_deposit",
+                args,
+                attached_balance: sdk::NO_DEPOSIT,
+                attached_gas: GAS_FOR_FT_ON_DEPOSIT,
+            });
+            let rollback = FinishEngineDepositCallArgs {
+                proof_key: receipt.proof_key,
+                used_event_index,
+                trace_id: data.trace_id,
+                engine_account_id: data.new_owner_id,
+                amount: recipient_amount,
+                treasury_id,
+                treasury_fee,
+                relayer_id: data.relayer_id,
+                relayer_fee,
+                protocol_fee,
+                sender: data.sender,
+                minted_at,
+            }
+            .try_to_vec()
+            .unwrap();
+            let promise1 = handler.promise_attach_callback(
+                promise0,
+                &PromiseCreateArgs {
+                    target_account_id: sdk::current_account_id(),
+                    method: b"finish_engine_deposit",
+                    args: rollback,
+                    attached_balance: sdk::NO_DEPOSIT,
+                    attached_gas: GAS_FOR_FINISH_ENGINE_DEPOSIT,
+                },
+            );
+            handler.promise_return(promise1);
+        } else if let Some(msg) = data.msg {
+            let args = FtOnTransfer {
+                amount: recipient_amount,
+                msg,
+                receiver_id: data.new_owner_id.clone(),
+            }
+            .try_to_vec()
+            .unwrap();
+            let promise0 = handler.promise_create_call(&PromiseCreateArgs {
+                target_account_id: data.new_owner_id.clone(),
+                method: b"ft_on_transfer",
+                args,
+                attached_balance: sdk::NO_DEPOSIT,
+                attached_gas: GAS_FOR_FT_TRANSFER_CALL - GAS_FOR_RESOLVE_TRANSFER,
+            });
+            let args = FinishDepositTransferCallArgs {
+                trace_id: data.trace_id,
+                receiver_id: data.new_owner_id,
+                amount: recipient_amount,
+            }
+            .try_to_vec()
+            .unwrap();
+            let promise1 = handler.promise_attach_callback(
+                promise0,
+                &PromiseCreateArgs {
+                    target_account_id: sdk::current_account_id(),
+                    method: b"finish_deposit_transfer",
+                    args,
+                    attached_balance: sdk::NO_DEPOSIT,
+                    attached_gas: GAS_FOR_RESOLVE_TRANSFER,
+                },
+            );
+            handler.promise_return(promise1);
+        } else if data.skip_used {
+            handler.value_return(&DepositStatus::Deposited.try_to_vec().unwrap()[..]);
+        }
+    }
+
+    /// Deposits to the configured engine account carrying a message are credited inside the engine.
+    fn is_engine_deposit(&self, new_owner_id: &str, msg: &Option<String>) -> bool {
+        msg.is_some() && self.contract.engine_account.as_deref() == Some(new_owner_id)
+    }
+
+    /// Relayer part of the deposit fee, nothing when the treasury relayed the proof itself.
+    /// It is a payout, so it rounds down and the treasury keeps the remainder.
+    fn relayer_fee(&self, treasury_id: &str, relayer_id: &str, fee: Balance) -> Balance {
+        if relayer_id == treasury_id {
+            return 0;
+        }
+        bps_payout_floor(fee, self.contract.relayer_fee_share_bps)
+    }
+
+    fn record_eth_block_height(&mut self, proof: &Proof) -> u64 {
+        let height = proof
+            .block_number()
+            .unwrap_or_else(|err| sdk::panic_utf8(err.as_ref()));
+        if height > self.contract.last_eth_block_height {
+            self.contract.last_eth_block_height = height;
+        }
+        height
+    }
+
+    /// Callback of the engine `ft_on_deposit` hook. When the engine fails to credit the
+    /// recipient, the deposit is rolled back so its proof can be submitted again.
+    pub fn finish_engine_deposit(&mut self) {
+        sdk::assert_private_call();
+        let data = FinishEngineDepositCallArgs::try_from_slice(&sdk::read_input()).unwrap();
+        if sdk::promise_results_count() != 1 {
+            sdk::panic_utf8(ConnectorError::UnexpectedPromiseResults.as_ref());
+        }
+        match sdk::promise_result(0) {
+            PromiseResult::Successful(x) => sdk::value_return(&x[..]),
+            _ => self.rollback_engine_deposit(data),
+        }
+    }
+
+    /// Callback of the `ft_on_transfer` call forwarding a deposit with a message. A failed
+    /// call, e.g. to an account without `ft_on_transfer`, falls back to a plain deposit:
+    /// the recipient keeps the tokens. Otherwise unused tokens go back to the connector.
+    pub fn finish_deposit_transfer(&mut self) {
+        sdk::assert_private_call();
+        let data = FinishDepositTransferCallArgs::try_from_slice(&sdk::read_input()).unwrap();
+        if sdk::promise_results_count() != 1 {
+            sdk::panic_utf8(ConnectorError::UnexpectedPromiseResults.as_ref());
+        }
+        let used_amount = match sdk::promise_result(0) {
+            PromiseResult::Successful(_) => {
+                let amount = self.ft.ft_resolve_transfer(
+                    sdk::current_account_id(),
+                    data.receiver_id,
+                    data.amount,
+                );
+                self.save_contract();
+                amount
+            }
+            _ => {
+                events::emit_deposit_transfer_fallback(
+                    &data.trace_id,
+                    &data.receiver_id,
+                    data.amount,
+                );
+                data.amount
+            }
+        };
+        sdk::value_return(&used_amount.to_string().as_bytes());
+    }
+
+    /// Burn what the deposit minted, forget its proof and history entry, and refund the
+    /// storage freed by the proof record to the relayer. Panics, keeping the deposit, if
+    /// a fee was already moved away.
+    fn rollback_engine_deposit(&mut self, data: FinishEngineDepositCallArgs) {
+        self.burn(data.engine_account_id.clone(), data.amount);
+        self.burn(data.treasury_id, data.treasury_fee);
+        if data.relayer_fee > 0 {
+            self.burn(data.relayer_id.clone(), data.relayer_fee);
+        }
+        self.contract.accrued_fees -= data.protocol_fee;
+        let initial_storage = sdk::storage_usage();
+        sdk::remove_storage(self.used_event_key(&data.proof_key).as_bytes());
+        sdk::remove_storage(self.used_event_index_key(data.used_event_index).as_bytes());
+        let refund = Balance::from(initial_storage.saturating_sub(sdk::storage_usage()))
+            * sdk::STORAGE_PRICE_PER_BYTE;
+        self.remove_history(&data.engine_account_id, &data.sender, data.minted_at);
+        sdk::remove_storage(self.deposit_receipt_key(&data.trace_id).as_bytes());
+        sdk::remove_storage(self.deposit_proof_key(&data.proof_key).as_bytes());
+        if refund > 0 {
+            let promise0 = sdk::promise_batch_create(data.relayer_id);
+            sdk::promise_batch_action_transfer(promise0, refund);
+        }
+        events::emit_deposit_rolled_back(&data.proof_key, &data.trace_id, &data.engine_account_id);
+        self.save_contract();
     }
 
-    fn record_proof(&mut self, key: String) -> Balance {
+    /// Storage of the record is paid for by the deposit charged in `deposit`.
+    /// Returns the index of the record in the pruning index.
+    fn record_proof(
+        &mut self,
+        key: String,
+        eth_block_height: u64,
+        submitter_id: &AccountId,
+        prover_account: &AccountId,
+    ) -> u64 {
         #[cfg(feature = "log")]
         sdk::log("Record proof".into());
-        let initial_storage = sdk::storage_usage();
         let key = key.as_str();
 
-        assert!(
-            !self.check_used_event(key),
-            "Proof event cannot be reused. Proof already exist."
-        );
-        self.save_used_event(key);
-        let current_storage = sdk::storage_usage();
-        let attached_deposit = sdk::attached_deposit();
-        let required_deposit =
-            Balance::from(current_storage - initial_storage) * sdk::STORAGE_PRICE_PER_BYTE;
-        attached_deposit - required_deposit
+        if self.check_used_event(key) {
+            sdk::panic_utf8(ConnectorError::ProofAlreadyUsed.as_ref());
+        }
+        let mut pruning = self.used_event_pruning();
+        let index = pruning.next_index;
+        pruning.next_index += 1;
+        self.save_used_event_pruning(&pruning);
+
+        let initial_storage = sdk::storage_usage();
+        self.save_used_event(key, Some(submitter_id), Some(prover_account));
+        let entry = UsedEventIndexEntry {
+            proof_key: key.into(),
+            eth_block_height,
+        };
+        sdk::save_contract(&self.used_event_index_key(index), &entry);
+        if self.contract.used_proof_storage_bytes.is_none() {
+            // Scale the first record to the longest key, account ids and index, later
+            // deposits are charged for this canonical record without measuring again
+            let measured = sdk::storage_usage() - initial_storage;
+            self.contract.used_proof_storage_bytes = Some(
+                measured
+                    - 2 * key.len() as u64
+                    - submitter_id.len() as u64
+                    - prover_account.len() as u64
+                    - index.to_string().len() as u64
+                    + 2 * MAX_PROOF_KEY_LEN
+                    + 2 * MAX_ACCOUNT_ID_LEN
+                    + MAX_INDEX_LEN,
+            );
+        }
+        index
+    }
+
+    /// Deposit to attach to `deposit` to pay for the used proof record.
+    fn used_proof_storage_deposit(&self) -> Balance {
+        let bytes = self
+            .contract
+            .used_proof_storage_bytes
+            .unwrap_or(USED_PROOF_STORAGE_BYTES);
+        Balance::from(bytes) * sdk::STORAGE_PRICE_PER_BYTE
     }
 
     fn mint(&mut self, owner_id: AccountId, amount: Balance) {
@@ -160,6 +982,7 @@ impl EthConnectorContract {
             self.ft.accounts_insert(owner_id.clone(), 0);
         }
         self.ft.internal_deposit(owner_id, amount);
+        self.contract.total_minted += amount;
         #[cfg(feature = "log")]
         sdk::log("Mint success".into());
     }
@@ -168,106 +991,1622 @@ impl EthConnectorContract {
         #[cfg(feature = "log")]
         sdk::log(format!("Burn {} tokens for: {}", amount, owner_id));
         self.ft.internal_withdraw(owner_id, amount);
+        self.contract.total_burned += amount;
     }
 
     pub fn withdraw(&mut self) {
+        self.assert_not_in_maintenance();
         #[cfg(feature = "log")]
         sdk::log("Start withdraw".into());
+        if self.is_paused(PAUSE_WITHDRAW) {
+            self.refuse(REFUSE_PAUSED, ConnectorError::Paused.as_ref());
+            return;
+        }
         let args: WithdrawCallArgs =
             WithdrawCallArgs::from(parse_json(&sdk::read_input()).expect(FAILED_PARSE));
-        let recipient_address = validate_eth_address(args.recipient_id);
-        let res = WithdrawResult {
-            recipient_id: recipient_address,
+        self.internal_withdraw(sdk::predecessor_account_id(), args);
+    }
+
+    /// Withdraw on behalf of a holder who approved the predecessor as withdrawal executor.
+    pub fn withdraw_for(&mut self) {
+        self.assert_not_in_maintenance();
+        if self.is_paused(PAUSE_WITHDRAW) {
+            self.refuse(REFUSE_PAUSED, ConnectorError::Paused.as_ref());
+            return;
+        }
+        let json = parse_json(&sdk::read_input()).expect(FAILED_PARSE);
+        let owner_id = json.string("owner_id").expect(FAILED_PARSE);
+        let args = WithdrawCallArgs::from(json);
+        let executor_id = sdk::predecessor_account_id();
+        let key = self.withdraw_allowance_key(&owner_id, &executor_id);
+        let allowance = self.get_withdraw_allowance_value(&key);
+        if allowance < args.amount {
+            self.refuse(
+                REFUSE_LIMIT_EXCEEDED,
+                ConnectorError::AllowanceExceeded.as_ref(),
+            );
+            return;
+        }
+        let amount = args.amount;
+        // A refused withdrawal doesn't spend the allowance
+        if self.internal_withdraw(owner_id, args) {
+            sdk::save_contract(&key, &(allowance - amount));
+        }
+    }
+
+    /// Pre-authorize withdrawals of up to `max_amount` of the predecessor's tokens to
+    /// `recipient_id` until `expires_at`, executed later by `executor_id`, or by any
+    /// account when unset. Must attach enough NEAR funds to cover for storage of the
+    /// intent. Returns the id of the intent.
+    pub fn authorize_withdraw_intent(&mut self) {
+        self.assert_not_in_maintenance();
+        let args = AuthorizeWithdrawIntentCallArgs::from(
+            parse_json(&sdk::read_input()).expect(FAILED_PARSE),
+        );
+        EthAddress::try_from(args.recipient_id.as_str())
+            .unwrap_or_else(|err| sdk::panic_utf8(err.as_ref()));
+        assert!(args.max_amount > 0, "Intent amount should be positive");
+        assert!(
+            args.expires_at > sdk::block_timestamp(),
+            "Intent expiry should be in the future"
+        );
+        let intent = WithdrawIntent {
+            owner_id: sdk::predecessor_account_id(),
+            executor_id: args.executor_id,
+            recipient_id: args.recipient_id,
+            remaining_amount: args.max_amount,
+            expires_at: args.expires_at,
+        };
+        let id = self.withdraw_intents_count();
+        let initial_storage = sdk::storage_usage();
+        sdk::save_contract(&self.withdraw_intent_key(id), &intent);
+        sdk::save_contract(&self.withdraw_intents_count_key(), &(id + 1));
+        Self::charge_storage_deposit(
+            Balance::from(sdk::storage_usage() - initial_storage) * sdk::STORAGE_PRICE_PER_BYTE,
+        );
+        sdk::value_return(&id.try_to_vec().unwrap()[..]);
+    }
+
+    /// Withdraw `amount` of a pre-authorized intent to its Ethereum recipient. A fully
+    /// used intent is removed and its storage refunded to the intent owner.
+    pub fn execute_withdraw_intent(&mut self) {
+        self.assert_not_in_maintenance();
+        if self.is_paused(PAUSE_WITHDRAW) {
+            self.refuse(REFUSE_PAUSED, ConnectorError::Paused.as_ref());
+            return;
+        }
+        let args = ExecuteWithdrawIntentCallArgs::from(
+            parse_json(&sdk::read_input()).expect(FAILED_PARSE),
+        );
+        let mut intent = self
+            .get_withdraw_intent_value(args.id)
+            .unwrap_or_else(|| sdk::panic_utf8(b"Withdraw intent not found"));
+        if let Some(executor_id) = &intent.executor_id {
+            assert_eq!(
+                &sdk::predecessor_account_id(),
+                executor_id,
+                "Only the intent executor can execute it"
+            );
+        }
+        assert!(
+            sdk::block_timestamp() < intent.expires_at,
+            "Withdraw intent expired"
+        );
+        if intent.remaining_amount < args.amount {
+            self.refuse(
+                REFUSE_LIMIT_EXCEEDED,
+                ConnectorError::AllowanceExceeded.as_ref(),
+            );
+            return;
+        }
+        let withdraw_args = WithdrawCallArgs {
+            recipient_id: intent.recipient_id.clone(),
             amount: args.amount,
-            eth_custodian_address: self.contract.eth_custodian_address,
+            memo: args.memo,
+        };
+        // A refused withdrawal leaves the intent untouched
+        if !self.internal_withdraw(intent.owner_id.clone(), withdraw_args) {
+            return;
+        }
+        intent.remaining_amount -= args.amount;
+        if intent.remaining_amount == 0 {
+            self.remove_withdraw_intent(args.id, intent.owner_id);
+        } else {
+            sdk::save_contract(&self.withdraw_intent_key(args.id), &intent);
+        }
+    }
+
+    /// Revoke an intent of the predecessor account and refund its storage.
+    pub fn revoke_withdraw_intent(&mut self) {
+        self.assert_not_in_maintenance();
+        let args =
+            WithdrawIntentIdCallArgs::from(parse_json(&sdk::read_input()).expect(FAILED_PARSE));
+        let account_id = sdk::predecessor_account_id();
+        assert_eq!(
+            self.get_withdraw_intent_value(args.id)
+                .map(|intent| intent.owner_id),
+            Some(account_id.clone()),
+            "Only the intent owner can revoke it"
+        );
+        self.remove_withdraw_intent(args.id, account_id);
+    }
+
+    pub fn get_withdraw_intent(&self) {
+        let args =
+            WithdrawIntentIdCallArgs::from(parse_json(&sdk::read_input()).expect(FAILED_PARSE));
+        let res = self
+            .get_withdraw_intent_value(args.id)
+            .try_to_vec()
+            .unwrap();
+        sdk::value_return(&res[..]);
+    }
+
+    /// Burn the tokens and return the withdrawal result. Returns `false` when the
+    /// withdrawal was refused without a panic, as the refusal policy allows.
+    fn internal_withdraw(&mut self, owner_id: AccountId, args: WithdrawCallArgs) -> bool {
+        let recipient_address = EthAddress::try_from(args.recipient_id.as_str())
+            .unwrap_or_else(|err| sdk::panic_utf8(err.as_ref()));
+        if self.is_blocked_eth_recipient(&recipient_address) {
+            self.refuse(
+                REFUSE_BLOCKLISTED,
+                ConnectorError::BlockedEthRecipient.as_ref(),
+            );
+            return false;
+        }
+        // Reject dust withdrawals costing more in Ethereum gas than they're worth
+        if args.amount < self.contract.min_withdraw_amount {
+            self.refuse(
+                REFUSE_LIMIT_EXCEEDED,
+                ConnectorError::WithdrawBelowMinimum.as_ref(),
+            );
+            return false;
+        }
+        // The fee stays locked on Ethereum and accrues until claimed
+        let fee = bps_fee_ceil(args.amount, self.contract.withdraw_fee_bps);
+        self.contract.accrued_fees += fee;
+        let res = WithdrawResult {
+            recipient_id: recipient_address.0,
+            amount: args.amount - fee,
+            eth_custodian_address: self.contract.eth_custodian_address.0,
+            memo: args.memo.unwrap_or_default(),
         }
         .try_to_vec()
         .unwrap();
         // Burn tokens to recipient
-        self.burn(sdk::predecessor_account_id(), args.amount);
+        self.burn(owner_id.clone(), args.amount);
+        self.push_history(
+            &owner_id,
+            HistoryEntry {
+                kind: HistoryKind::Withdraw,
+                amount: args.amount,
+                fee,
+                eth_address: hex::encode(recipient_address.0),
+                timestamp: sdk::block_timestamp(),
+            },
+        );
+        events::emit_withdraw(
+            &owner_id,
+            &recipient_address,
+            args.amount,
+            fee,
+            &self.contract.config_hash(),
+        );
         // Save new contract data
         self.save_contract();
         sdk::value_return(&res[..]);
+        true
     }
 
-    pub fn ft_total_supply(&self) {
-        let total_supply = self.ft.ft_total_supply();
-        sdk::value_return(&total_supply.to_string().as_bytes());
-        #[cfg(feature = "log")]
-        sdk::log(format!("Total supply: {}", total_supply));
-    }
-
-    pub fn ft_balance_of(&self) {
-        let args = BalanceOfCallArgs::from(parse_json(&sdk::read_input()).expect(FAILED_PARSE));
-        let balance = self.ft.ft_balance_of(args.account_id.clone());
-        sdk::value_return(&balance.to_string().as_bytes());
-        #[cfg(feature = "log")]
-        sdk::log(format!("Balance [{}]: {}", args.account_id, balance));
-    }
-
-    pub fn ft_transfer(&mut self) {
-        let args: TransferCallArgs =
-            TransferCallArgs::from(parse_json(&sdk::read_input()).expect(FAILED_PARSE));
-
-        self.ft
-            .ft_transfer(args.receiver_id.clone(), args.amount, args.memo.clone());
+    /// First step of an ownership transfer, completed by the proposed owner calling
+    /// `accept_owner`. A new proposal replaces the pending one.
+    pub fn propose_owner(&mut self) {
+        self.assert_owner();
+        let args = OwnerCallArgs::from(parse_json(&sdk::read_input()).expect(FAILED_PARSE));
+        self.contract.proposed_owner_id = Some(args.owner_id);
         self.save_contract();
-        #[cfg(feature = "log")]
-        sdk::log(format!(
-            "Transfer amount {} to {} success with memo: {:?}",
-            args.amount, args.receiver_id, args.memo
-        ));
     }
 
-    pub fn ft_resolve_transfer(&mut self) {
-        sdk::assert_private_call();
-        let args: ResolveTransferCallArgs =
-            ResolveTransferCallArgs::try_from_slice(&sdk::read_input()).unwrap();
-        let amount = self.ft.ft_resolve_transfer(
-            args.sender_id.clone(),
-            args.receiver_id.clone(),
-            args.amount,
+    pub fn accept_owner(&mut self) {
+        let predecessor_account_id = sdk::predecessor_account_id();
+        assert_eq!(
+            self.contract.proposed_owner_id.as_ref(),
+            Some(&predecessor_account_id),
+            "Only the proposed owner can accept ownership"
         );
-        // `ft_resolve_transfer` can changed `total_supply` so we should save contract
+        self.contract.owner_id = predecessor_account_id;
+        self.contract.proposed_owner_id = None;
         self.save_contract();
-        sdk::value_return(&amount.to_string().as_bytes());
-        #[cfg(feature = "log")]
-        sdk::log(format!(
-            "Resolve transfer of {} from {} to {} success",
-            args.amount, args.sender_id, args.receiver_id
-        ));
     }
 
-    pub fn ft_transfer_call(&mut self) {
-        let args: TransferCallCallArgs =
-            TransferCallCallArgs::from(parse_json(&sdk::read_input()).expect(FAILED_PARSE));
-        #[cfg(feature = "log")]
-        sdk::log(format!(
-            "Transfer call to {} amount {}",
-            args.receiver_id, args.amount,
-        ));
+    pub fn get_owner_id(&self) {
+        let res = self.contract.owner_id.try_to_vec().unwrap();
+        sdk::value_return(&res[..]);
+    }
 
-        self.ft.ft_transfer_call(
-            args.receiver_id.clone(),
-            args.amount,
-            args.memo.clone(),
-            args.msg.clone(),
+    /// Block or unblock withdrawals to an Ethereum address, e.g. a sanctioned
+    /// or known-compromised destination.
+    pub fn set_eth_recipient_blocked(&mut self) {
+        self.assert_owner();
+        let args = SetEthRecipientBlockedCallArgs::from(
+            parse_json(&sdk::read_input()).expect(FAILED_PARSE),
         );
+        let key = self.blocked_eth_recipient_key(&args.eth_address);
+        if args.blocked {
+            sdk::save_contract(key.as_str(), &0u8);
+        } else {
+            sdk::remove_storage(key.as_bytes());
+        }
+        events::emit_eth_recipient_block(&args.eth_address, args.blocked);
     }
 
-    pub fn storage_deposit(&mut self) {
-        let args: StorageDepositCallArgs =
-            StorageDepositCallArgs::from(parse_json(&sdk::read_input()).expect(FAILED_PARSE));
+    pub fn is_eth_recipient_blocked(&self) {
+        let args = EthRecipientCallArgs::from(parse_json(&sdk::read_input()).expect(FAILED_PARSE));
         let res = self
-            .ft
-            .storage_deposit(args.account_id, args.registration_only)
+            .is_blocked_eth_recipient(&args.eth_address)
             .try_to_vec()
             .unwrap();
-        self.save_contract();
         sdk::value_return(&res[..]);
     }
 
-    pub fn storage_withdraw(&mut self) {
+    /// Allow an executor to withdraw up to `amount` of the predecessor's tokens.
+    /// Replaces any previous allowance of the executor. Must attach enough NEAR
+    /// funds to cover for storage of the allowance.
+    pub fn approve_withdraw_executor(&mut self) {
+        self.assert_not_in_maintenance();
+        let args =
+            WithdrawAllowanceCallArgs::from(parse_json(&sdk::read_input()).expect(FAILED_PARSE));
+        let key = self.withdraw_allowance_key(&sdk::predecessor_account_id(), &args.executor_id);
+        let initial_storage = sdk::storage_usage();
+        sdk::save_contract(&key, &args.amount);
+        let required_deposit = Balance::from(sdk::storage_usage().saturating_sub(initial_storage))
+            * sdk::STORAGE_PRICE_PER_BYTE;
+        assert!(
+            sdk::attached_deposit() >= required_deposit,
+            "Not enough attached deposit to cover allowance storage"
+        );
+    }
+
+    /// Revoke the allowance of an executor and refund its storage.
+    pub fn revoke_withdraw_executor(&mut self) {
+        self.assert_not_in_maintenance();
+        let args =
+            WithdrawExecutorCallArgs::from(parse_json(&sdk::read_input()).expect(FAILED_PARSE));
+        let account_id = sdk::predecessor_account_id();
+        let key = self.withdraw_allowance_key(&account_id, &args.executor_id);
+        assert!(sdk::storage_has_key(&key), "Executor has no allowance");
+        let initial_storage = sdk::storage_usage();
+        sdk::remove_storage(key.as_bytes());
+        let refund =
+            Balance::from(initial_storage - sdk::storage_usage()) * sdk::STORAGE_PRICE_PER_BYTE;
+        if refund > 0 {
+            let promise0 = sdk::promise_batch_create(account_id);
+            sdk::promise_batch_action_transfer(promise0, refund);
+        }
+    }
+
+    pub fn get_withdraw_allowance(&self) {
+        let args =
+            GetWithdrawAllowanceCallArgs::from(parse_json(&sdk::read_input()).expect(FAILED_PARSE));
+        let key = self.withdraw_allowance_key(&args.owner_id, &args.executor_id);
+        let allowance = self.get_withdraw_allowance_value(&key);
+        sdk::value_return(allowance.to_string().as_bytes());
+    }
+
+    /// Register an alias for the predecessor account. Ethereum users can then put
+    /// `@<alias>` in the deposit recipient field instead of the full account name.
+    /// Aliases can't be released, so deposits to them can't be redirected, and deposits
+    /// from Ethereum blocks up to the light client head at registration are never
+    /// resolved to it. Needs the light client of `set_block_confirmations`, the height
+    /// of the last accepted proof lags too far behind Ethereum to anchor on.
+    /// Must attach enough NEAR funds to cover for storage of the alias.
+    pub fn register_alias(&mut self) {
+        self.assert_not_in_maintenance();
+        let args = AliasCallArgs::from(parse_json(&sdk::read_input()).expect(FAILED_PARSE));
+        Self::assert_valid_alias(&args.alias);
+        let key = self.alias_key(&args.alias);
+        assert!(!sdk::storage_has_key(&key), "Alias already registered");
+        let light_client_account = self
+            .block_confirmations()
+            .map(|block_confirmations| block_confirmations.light_client_account)
+            .unwrap_or_else(|| sdk::panic_utf8(b"Aliases need a light client"));
+
+        let account_id = sdk::predecessor_account_id();
+        let initial_storage = sdk::storage_usage();
+        // Reserved without resolving until the light client height is known
+        let record = AliasRecord {
+            account_id: account_id.clone(),
+            eth_block_height: u64::MAX,
+        };
+        sdk::save_contract(&key, &record);
+        Self::charge_storage_deposit(
+            Balance::from(sdk::storage_usage() - initial_storage) * sdk::STORAGE_PRICE_PER_BYTE,
+        );
+        #[cfg(feature = "log")]
+        sdk::log(format!(
+            "Alias {} registered for {}",
+            args.alias, account_id
+        ));
+        let promise0 = sdk::promise_create(
+            light_client_account,
+            b"last_block_number",
+            &[],
+            sdk::NO_DEPOSIT,
+            GAS_FOR_LAST_BLOCK_NUMBER,
+        );
+        let promise1 = sdk::promise_then(
+            promise0,
+            sdk::current_account_id(),
+            b"finish_register_alias",
+            &args.try_to_vec().unwrap(),
+            sdk::NO_DEPOSIT,
+            GAS_FOR_FINISH_REGISTER_ALIAS,
+        );
+        sdk::promise_return(promise1);
+    }
+
+    /// Callback of `register_alias` with the light client head. When the light client
+    /// fails, the alias is released and its storage refunded.
+    pub fn finish_register_alias(&mut self) {
+        sdk::assert_private_call();
+        let args = AliasCallArgs::try_from_slice(&sdk::read_input()).unwrap();
+        let key = self.alias_key(&args.alias);
+        let mut record = self
+            .get_alias(&args.alias)
+            .unwrap_or_else(|| sdk::panic_utf8(b"Alias is not registered"));
+        let last_block_number = match sdk::promise_result(0) {
+            PromiseResult::Successful(x) => u64::try_from_slice(&x).ok(),
+            _ => None,
+        };
+        match last_block_number {
+            Some(height) => {
+                record.eth_block_height = height;
+                sdk::save_contract(&key, &record);
+            }
+            None => {
+                let initial_storage = sdk::storage_usage();
+                sdk::remove_storage(key.as_bytes());
+                let refund = Balance::from(initial_storage - sdk::storage_usage())
+                    * sdk::STORAGE_PRICE_PER_BYTE;
+                let promise0 = sdk::promise_batch_create(record.account_id);
+                sdk::promise_batch_action_transfer(promise0, refund);
+            }
+        }
+    }
+
+    pub fn get_alias_account(&self) {
+        let args = AliasCallArgs::from(parse_json(&sdk::read_input()).expect(FAILED_PARSE));
+        let res = self
+            .get_alias(&args.alias)
+            .map(|record| record.account_id)
+            .try_to_vec()
+            .unwrap();
+        sdk::value_return(&res[..]);
+    }
+
+    /// Register a webhook owned by the predecessor account and return its id.
+    /// Must attach enough NEAR funds to cover for storage of the descriptor.
+    pub fn register_webhook(&mut self) {
+        self.assert_not_in_maintenance();
+        let args =
+            RegisterWebhookCallArgs::from(parse_json(&sdk::read_input()).expect(FAILED_PARSE));
+        let id = self.webhooks_count();
+        let initial_storage = sdk::storage_usage();
+        sdk::save_contract(
+            &self.webhook_key(id),
+            &Self::webhook_descriptor(sdk::predecessor_account_id(), args),
+        );
+        sdk::save_contract(&self.webhooks_count_key(), &(id + 1));
+        Self::charge_storage_deposit(
+            Balance::from(sdk::storage_usage() - initial_storage) * sdk::STORAGE_PRICE_PER_BYTE,
+        );
+        sdk::value_return(&id.try_to_vec().unwrap()[..]);
+    }
+
+    /// Replace the URL hash and filters of a webhook owned by the predecessor account.
+    /// Storage growth is charged to the attached deposit, freed storage is refunded.
+    pub fn update_webhook(&mut self) {
+        self.assert_not_in_maintenance();
+        let args = UpdateWebhookCallArgs::from(parse_json(&sdk::read_input()).expect(FAILED_PARSE));
+        let account_id = sdk::predecessor_account_id();
+        assert_eq!(
+            self.get_webhook_descriptor(args.id)
+                .map(|webhook| webhook.owner_id),
+            Some(account_id.clone()),
+            "Only the webhook owner can update it"
+        );
+
+        let initial_storage = sdk::storage_usage();
+        sdk::save_contract(
+            &self.webhook_key(args.id),
+            &Self::webhook_descriptor(account_id.clone(), args.webhook),
+        );
+        let storage_usage = sdk::storage_usage();
+        if storage_usage >= initial_storage {
+            Self::charge_storage_deposit(
+                Balance::from(storage_usage - initial_storage) * sdk::STORAGE_PRICE_PER_BYTE,
+            );
+        } else {
+            let refund = Balance::from(initial_storage - storage_usage)
+                * sdk::STORAGE_PRICE_PER_BYTE
+                + sdk::attached_deposit();
+            let promise0 = sdk::promise_batch_create(account_id);
+            sdk::promise_batch_action_transfer(promise0, refund);
+        }
+    }
+
+    /// Remove a webhook and refund its storage to the webhook owner. The contract owner
+    /// can remove any webhook.
+    pub fn remove_webhook(&mut self) {
+        self.assert_not_in_maintenance();
+        let args = WebhookIdCallArgs::from(parse_json(&sdk::read_input()).expect(FAILED_PARSE));
+        let webhook = self
+            .get_webhook_descriptor(args.id)
+            .unwrap_or_else(|| sdk::panic_utf8(b"Webhook not found"));
+        let predecessor_account_id = sdk::predecessor_account_id();
+        assert!(
+            predecessor_account_id == webhook.owner_id
+                || &predecessor_account_id == self.get_owner(),
+            "Only the webhook owner can remove it"
+        );
+
+        let initial_storage = sdk::storage_usage();
+        sdk::remove_storage(self.webhook_key(args.id).as_bytes());
+        let refund =
+            Balance::from(initial_storage - sdk::storage_usage()) * sdk::STORAGE_PRICE_PER_BYTE;
+        if refund > 0 {
+            let promise0 = sdk::promise_batch_create(webhook.owner_id);
+            sdk::promise_batch_action_transfer(promise0, refund);
+        }
+    }
+
+    pub fn get_webhook(&self) {
+        let args = WebhookIdCallArgs::from(parse_json(&sdk::read_input()).expect(FAILED_PARSE));
+        let res = self.get_webhook_descriptor(args.id).try_to_vec().unwrap();
+        sdk::value_return(&res[..]);
+    }
+
+    /// Paginated list of registered webhooks with their ids, by id. Removed webhooks
+    /// leave gaps, so a page may hold fewer than `limit` entries.
+    pub fn get_webhooks(&self) {
+        let args = GetWebhooksCallArgs::from(parse_json(&sdk::read_input()).expect(FAILED_PARSE));
+        let to_index = args
+            .from_index
+            .saturating_add(args.limit.min(MAX_ACCOUNTS_PAGE))
+            .min(self.webhooks_count());
+        let res: Vec<(u64, WebhookDescriptor)> = (args.from_index..to_index)
+            .filter_map(|id| self.get_webhook_descriptor(id).map(|webhook| (id, webhook)))
+            .collect();
+        sdk::value_return(&res.try_to_vec().unwrap()[..]);
+    }
+
+    /// Change which `Deposited` event params are treated as indexed, to follow
+    /// the layout of the linked custodian contract.
+    pub fn set_deposited_event_layout(&mut self) {
+        self.assert_owner();
+        self.assert_not_timelocked();
+        let layout = EventLayout::from(parse_json(&sdk::read_input()).expect(FAILED_PARSE));
+        self.apply_unstaged_action(AdminAction::SetDepositedEventLayout(layout));
+    }
+
+    pub fn get_deposited_event_layout(&self) {
+        let res = self.contract.deposited_event_layout.try_to_vec().unwrap();
+        sdk::value_return(&res[..]);
+    }
+
+    /// Set or unset the engine account which receives `ft_on_deposit` calls for
+    /// deposits addressed to it.
+    pub fn set_engine_account(&mut self) {
+        self.assert_owner();
+        self.assert_not_timelocked();
+        let args =
+            SetEngineAccountCallArgs::from(parse_json(&sdk::read_input()).expect(FAILED_PARSE));
+        self.apply_unstaged_action(AdminAction::SetEngineAccount(args.engine_account));
+    }
+
+    /// Set or unset the bridge registry this connector reports itself to, with the
+    /// chain id of the Ethereum network it bridges from.
+    pub fn set_registry(&mut self) {
+        self.assert_owner();
+        let args = SetRegistryCallArgs::from(parse_json(&sdk::read_input()).expect(FAILED_PARSE));
+        self.contract.registry = args.account_id.map(|account_id| RegistryConfig {
+            account_id,
+            eth_chain_id: args.eth_chain_id,
+            last_registered_at: 0,
+        });
+        self.save_contract();
+    }
+
+    /// Report the connector to the bridge registry, so wallets can discover it. Anyone
+    /// can refresh the registration once per interval, the owner at any time.
+    pub fn register_self(&mut self) {
+        let now = sdk::block_timestamp();
+        let is_owner = &sdk::predecessor_account_id() == self.get_owner();
+        let config_hash = self.contract.config_hash();
+        let eth_custodian_address = hex::encode(self.contract.eth_custodian_address.0);
+        let registry = self
+            .contract
+            .registry
+            .as_mut()
+            .unwrap_or_else(|| sdk::panic_utf8(b"Registry is not set"));
+        assert!(
+            is_owner || now >= registry.last_registered_at + REGISTRY_REFRESH_INTERVAL,
+            "Registration was refreshed recently"
+        );
+        registry.last_registered_at = now;
+        let args = format!(
+            r#"{{"chain_id":{},"eth_custodian_address":"{}","token_symbol":"{}","contract_version":"{}","config_hash":"{}"}}"#,
+            registry.eth_chain_id, eth_custodian_address, FT_SYMBOL, CONTRACT_VERSION, config_hash
+        );
+        let promise0 = sdk::promise_create(
+            registry.account_id.clone(),
+            b"register_bridge",
+            args.as_bytes(),
+            sdk::NO_DEPOSIT,
+            GAS_FOR_REGISTER_BRIDGE,
+        );
+        self.save_contract();
+        sdk::promise_return(promise0);
+    }
+
+    pub fn get_registry(&self) {
+        let res = self.contract.registry.try_to_vec().unwrap();
+        sdk::value_return(&res[..]);
+    }
+
+    /// Replace the custodian whose events are accepted in proofs. The replaced one is
+    /// recorded in the custodian history and, with `grace_blocks` or an explicit
+    /// `grace_until_eth_block`, stays accepted for deposits made before the switch.
+    pub fn set_eth_custodian_address(&mut self) {
+        self.assert_owner();
+        self.assert_not_timelocked();
+        let args = SetEthCustodianAddressCallArgs::from(
+            parse_json(&sdk::read_input()).expect(FAILED_PARSE),
+        );
+        self.validate_admin_action(&AdminAction::SetEthCustodianAddress(args.clone()));
+        self.replace_eth_custodian(args);
+        self.save_contract();
+    }
+
+    /// Send deposit proofs to a new prover deployment. Proofs already recorded keep the
+    /// prover which verified them and stay used.
+    pub fn set_prover_account(&mut self) {
+        self.assert_owner();
+        self.assert_not_timelocked();
+        let args =
+            SetProverAccountCallArgs::from(parse_json(&sdk::read_input()).expect(FAILED_PARSE));
+        let action = AdminAction::SetProverAccount(args.prover_account);
+        self.validate_admin_action(&action);
+        self.apply_admin_action(action);
+        self.save_contract();
+    }
+
+    /// Replaced custodians, oldest first.
+    pub fn get_custodian_history(&self) {
+        let res = self.custodian_history().try_to_vec().unwrap();
+        sdk::value_return(&res[..]);
+    }
+
+    pub fn get_custodian_info(&self) {
+        let res = CustodianInfo {
+            eth_custodian_address: self.contract.eth_custodian_address.0,
+            custodian_history: self.custodian_history(),
+            eth_chain_id: self
+                .contract
+                .registry
+                .as_ref()
+                .map(|registry| registry.eth_chain_id),
+            prover_account: self.contract.prover_account.clone(),
+            finality_horizon_blocks: self.used_event_pruning().horizon_blocks,
+            block_confirmations: self.block_confirmations(),
+            contract_version: CONTRACT_VERSION.into(),
+            state_version: STATE_VERSION,
+            paused_mask: self.get_paused(),
+            relayer_fee_share_bps: self.contract.relayer_fee_share_bps,
+        }
+        .try_to_vec()
+        .unwrap();
+        sdk::value_return(&res[..]);
+    }
+
+    /// Set the share of deposit fees paid to third-party relayers.
+    pub fn set_relayer_fee_share(&mut self) {
+        self.assert_owner();
+        self.assert_not_timelocked();
+        self.assert_not_frozen(FREEZE_FEES);
+        let args =
+            SetRelayerFeeShareCallArgs::from(parse_json(&sdk::read_input()).expect(FAILED_PARSE));
+        assert!(
+            args.relayer_fee_share_bps <= BPS_DENOMINATOR,
+            "Relayer fee share exceeds 100%"
+        );
+        self.contract.relayer_fee_share_bps = args.relayer_fee_share_bps;
+        self.save_contract();
+    }
+
+    /// Set the protocol fee charged on deposits, in basis points of the amount
+    /// left after the relayer fee. With `effective_at`, the change is scheduled instead.
+    pub fn set_deposit_fee(&mut self) {
+        self.assert_owner();
+        self.assert_not_timelocked();
+        self.assert_not_frozen(FREEZE_FEES);
+        let args = SetDepositFeeCallArgs::from(parse_json(&sdk::read_input()).expect(FAILED_PARSE));
+        assert!(
+            args.deposit_fee_bps <= MAX_DEPOSIT_FEE_BPS,
+            "Deposit fee exceeds the maximum"
+        );
+        match args.effective_at {
+            Some(effective_at) => {
+                Self::assert_in_future(effective_at);
+                self.contract.schedule_change(ScheduledParamChange {
+                    effective_at,
+                    change: ParamChange::DepositFee(args.deposit_fee_bps),
+                });
+            }
+            None => self.contract.deposit_fee_bps = args.deposit_fee_bps,
+        }
+        self.save_contract();
+    }
+
+    /// Set the fee charged on withdrawals, in basis points of the withdrawn amount,
+    /// and the minimum amount a withdrawal must burn. With `effective_at`, both
+    /// changes are scheduled instead.
+    pub fn set_withdraw_fee(&mut self) {
+        self.assert_owner();
+        self.assert_not_timelocked();
+        self.assert_not_frozen(FREEZE_FEES);
+        let args =
+            SetWithdrawFeeCallArgs::from(parse_json(&sdk::read_input()).expect(FAILED_PARSE));
+        assert!(
+            args.withdraw_fee_bps <= MAX_WITHDRAW_FEE_BPS,
+            "Withdraw fee exceeds the maximum"
+        );
+        match args.effective_at {
+            Some(effective_at) => {
+                Self::assert_in_future(effective_at);
+                self.contract.schedule_change(ScheduledParamChange {
+                    effective_at,
+                    change: ParamChange::WithdrawFee(args.withdraw_fee_bps),
+                });
+                self.contract.schedule_change(ScheduledParamChange {
+                    effective_at,
+                    change: ParamChange::MinWithdrawAmount(args.min_withdraw_amount),
+                });
+            }
+            None => {
+                self.contract.withdraw_fee_bps = args.withdraw_fee_bps;
+                self.contract.min_withdraw_amount = args.min_withdraw_amount;
+            }
+        }
+        self.save_contract();
+    }
+
+    /// Mint the accrued protocol fees to `recipient_id`.
+    pub fn claim_fees(&mut self) {
+        self.assert_owner();
+        let args = ClaimFeesCallArgs::from(parse_json(&sdk::read_input()).expect(FAILED_PARSE));
+        let amount = self.contract.accrued_fees;
+        assert!(amount > 0, "No fees to claim");
+        self.contract.accrued_fees = 0;
+        self.mint(args.recipient_id, amount);
+        self.save_contract();
+    }
+
+    pub fn get_fee_config(&self) {
+        let res = FeeConfig {
+            deposit_fee_bps: self.contract.deposit_fee_bps,
+            relayer_fee_share_bps: self.contract.relayer_fee_share_bps,
+            withdraw_fee_bps: self.contract.withdraw_fee_bps,
+            min_withdraw_amount: self.contract.min_withdraw_amount,
+            accrued_fees: self.contract.accrued_fees,
+        }
+        .try_to_vec()
+        .unwrap();
+        sdk::value_return(&res[..]);
+    }
+
+    /// Withdrawal limits applying to an account right now, so frontends can show
+    /// how much it can move without simulating the call.
+    pub fn get_effective_limits(&self) {
+        let args = BalanceOfCallArgs::from(parse_json(&sdk::read_input()).expect(FAILED_PARSE));
+        let balance = self.ft.ft_balance_of(args.account_id);
+        let withdraw_paused = self.is_paused(PAUSE_WITHDRAW);
+        let can_withdraw = !withdraw_paused
+            && self.contract.maintenance_banner.is_none()
+            && balance >= self.contract.min_withdraw_amount;
+        let res = EffectiveLimits {
+            balance,
+            deposit_paused: self.is_paused(PAUSE_DEPOSIT),
+            withdraw_paused,
+            min_withdraw_amount: self.contract.min_withdraw_amount,
+            max_withdraw_amount: if can_withdraw { balance } else { 0 },
+            withdraw_fee_bps: self.contract.withdraw_fee_bps,
+        }
+        .try_to_vec()
+        .unwrap();
+        sdk::value_return(&res[..]);
+    }
+
+    /// Schedule a new relayer fee share to take effect at the given block timestamp.
+    pub fn schedule_relayer_fee_share(&mut self) {
+        self.assert_owner();
+        self.assert_not_timelocked();
+        self.assert_not_frozen(FREEZE_FEES);
+        let args = ScheduleRelayerFeeShareCallArgs::from(
+            parse_json(&sdk::read_input()).expect(FAILED_PARSE),
+        );
+        assert!(
+            args.relayer_fee_share_bps <= BPS_DENOMINATOR,
+            "Relayer fee share exceeds 100%"
+        );
+        Self::assert_in_future(args.effective_at);
+        self.contract.schedule_change(ScheduledParamChange {
+            effective_at: args.effective_at,
+            change: ParamChange::RelayerFeeShare(args.relayer_fee_share_bps),
+        });
+        self.save_contract();
+    }
+
+    /// Schedule a new expected topic0 of the custodian `Deposited` event, e.g. ahead of
+    /// a custodian upgrade, to take effect at the given block timestamp.
+    pub fn schedule_deposited_event_topic(&mut self) {
+        self.assert_owner();
+        self.assert_not_timelocked();
+        let args =
+            ScheduleEventTopicCallArgs::from(parse_json(&sdk::read_input()).expect(FAILED_PARSE));
+        self.apply_unstaged_action(AdminAction::ScheduleDepositedEventTopic(args));
+    }
+
+    /// Topic0 hashes of the custodian events currently accepted in proofs.
+    pub fn get_event_topics(&self) {
+        let res = self.contract.event_topics.try_to_vec().unwrap();
+        sdk::value_return(&res[..]);
+    }
+
+    /// Permanently lock the parameters selected by the given flags, dropping their
+    /// pending scheduled changes. There is no way to unfreeze them.
+    pub fn irreversibly_freeze(&mut self) {
+        self.assert_owner();
+        let args = FreezeCallArgs::from(parse_json(&sdk::read_input()).expect(FAILED_PARSE));
+        self.contract.frozen_mask |= args.frozen_mask;
+        let frozen_mask = self.contract.frozen_mask;
+        self.contract
+            .scheduled_changes
+            .retain(|item| item.change.frozen_by() & frozen_mask == 0);
+        self.save_contract();
+    }
+
+    pub fn get_frozen_flags(&self) {
+        let res = self.contract.frozen_mask.try_to_vec().unwrap();
+        sdk::value_return(&res[..]);
+    }
+
+    /// Choose the guard classes which refuse calls with a `GuardRefusal` and a refund
+    /// of the attached deposit, the others panic.
+    pub fn set_refusal_policy(&mut self) {
+        self.assert_owner();
+        let args =
+            SetRefusalPolicyCallArgs::from(parse_json(&sdk::read_input()).expect(FAILED_PARSE));
+        sdk::save_contract(self.refusal_policy_key().as_str(), &args.refusal_mask);
+    }
+
+    pub fn get_refusal_policy(&self) {
+        let res = self.refusal_mask().try_to_vec().unwrap();
+        sdk::value_return(&res[..]);
+    }
+
+    /// Call `prover_account` alongside the primary prover for every deposit and count
+    /// how often both agree, to try a prover upgrade on real traffic before switching.
+    /// Unset with no `prover_account`. Resets the counters.
+    pub fn set_shadow_prover(&mut self) {
+        self.assert_owner();
+        let args =
+            SetShadowProverCallArgs::from(parse_json(&sdk::read_input()).expect(FAILED_PARSE));
+        self.contract.shadow_prover = args.prover_account.map(ShadowProver::new);
+        self.save_contract();
+    }
+
+    pub fn get_shadow_prover(&self) {
+        let res = self.contract.shadow_prover.try_to_vec().unwrap();
+        sdk::value_return(&res[..]);
+    }
+
+    /// Only mint deposits once `light_client_account` reports `min_block_confirmations`
+    /// blocks on top of the proof block, against short reorgs. Unset with no
+    /// `light_client_account` or zero confirmations.
+    pub fn set_block_confirmations(&mut self) {
+        self.assert_owner();
+        let args = SetBlockConfirmationsCallArgs::from(
+            parse_json(&sdk::read_input()).expect(FAILED_PARSE),
+        );
+        let key = self.block_confirmations_key();
+        match args.light_client_account {
+            Some(light_client_account) if args.min_block_confirmations > 0 => {
+                assert!(
+                    Self::is_valid_account_id(&light_client_account),
+                    "Invalid light client account"
+                );
+                let block_confirmations = BlockConfirmations {
+                    light_client_account,
+                    min_block_confirmations: args.min_block_confirmations,
+                };
+                sdk::save_contract(&key, &block_confirmations);
+            }
+            _ => sdk::remove_storage(key.as_bytes()),
+        }
+    }
+
+    pub fn get_block_confirmations(&self) {
+        let res = self.block_confirmations().try_to_vec().unwrap();
+        sdk::value_return(&res[..]);
+    }
+
+    /// Drop all parameter changes which have not taken effect yet.
+    pub fn cancel_scheduled_changes(&mut self) {
+        self.assert_owner();
+        self.contract.scheduled_changes.clear();
+        self.save_contract();
+    }
+
+    /// Set the delay between staging an admin action and executing it. While it is not
+    /// zero, fee, custodian, event format and engine changes, proof revocations and upgrades
+    /// only go through `stage_action`, and `migrate` is refused. Raising the delay is
+    /// immediate, lowering it has to be staged.
+    pub fn set_timelock_delay(&mut self) {
+        self.assert_owner();
+        let args =
+            SetTimelockDelayCallArgs::from(parse_json(&sdk::read_input()).expect(FAILED_PARSE));
+        let mut timelock = self.timelock();
+        assert!(
+            args.delay >= timelock.delay,
+            "Lowering the timelock delay must be staged"
+        );
+        timelock.delay = args.delay;
+        self.save_timelock(&timelock);
+    }
+
+    /// Stage an admin action, executable with `execute_action` once the timelock delay
+    /// has passed. Returns the id of the staged action.
+    pub fn stage_action(&mut self) {
+        self.assert_owner();
+        let args = StageActionCallArgs::from(parse_json(&sdk::read_input()).expect(FAILED_PARSE));
+        self.validate_admin_action(&args.action);
+        let mut timelock = self.timelock();
+        let id = timelock.next_id;
+        timelock.next_id += 1;
+        timelock.actions.push(StagedAction {
+            id,
+            action: args.action,
+            unlock_at: sdk::block_timestamp() + timelock.delay,
+        });
+        self.save_timelock(&timelock);
+        sdk::value_return(&id.try_to_vec().unwrap()[..]);
+    }
+
+    /// Execute an unlocked staged action. Staged upgrades are executed by `upgrade`,
+    /// which takes the code.
+    pub fn execute_action(&mut self) {
+        self.assert_owner();
+        let args = ActionIdCallArgs::from(parse_json(&sdk::read_input()).expect(FAILED_PARSE));
+        let mut timelock = self.timelock();
+        let index = timelock
+            .actions
+            .iter()
+            .position(|staged| staged.id == args.id)
+            .unwrap_or_else(|| sdk::panic_utf8(b"Staged action not found"));
+        let staged = timelock.actions.remove(index);
+        assert!(
+            staged.unlock_at <= sdk::block_timestamp(),
+            "Staged action is still locked"
+        );
+        match staged.action {
+            AdminAction::Upgrade(_) => sdk::panic_utf8(b"Staged upgrades are executed by upgrade"),
+            AdminAction::SetTimelockDelay(delay) => timelock.delay = delay,
+            action => {
+                // Parameters may have been frozen since the action was staged
+                self.validate_admin_action(&action);
+                self.apply_admin_action(action);
+                self.save_contract();
+            }
+        }
+        self.save_timelock(&timelock);
+    }
+
+    pub fn cancel_action(&mut self) {
+        self.assert_owner();
+        let args = ActionIdCallArgs::from(parse_json(&sdk::read_input()).expect(FAILED_PARSE));
+        let mut timelock = self.timelock();
+        let len = timelock.actions.len();
+        timelock.actions.retain(|staged| staged.id != args.id);
+        assert!(timelock.actions.len() < len, "Staged action not found");
+        self.save_timelock(&timelock);
+    }
+
+    pub fn get_timelock(&self) {
+        let res = self.timelock().try_to_vec().unwrap();
+        sdk::value_return(&res[..]);
+    }
+
+    /// Upcoming parameter changes, ordered by the time they take effect.
+    pub fn get_scheduled_changes(&self) {
+        let res = self.contract.scheduled_changes.try_to_vec().unwrap();
+        sdk::value_return(&res[..]);
+    }
+
+    /// Enter read-only maintenance mode with the given banner, or leave it when no banner is set.
+    /// State-changing user methods revert while views keep working.
+    pub fn set_maintenance_mode(&mut self) {
+        self.assert_owner();
+        let args =
+            SetMaintenanceModeCallArgs::from(parse_json(&sdk::read_input()).expect(FAILED_PARSE));
+        self.contract.maintenance_banner = args.banner;
+        self.save_contract();
+    }
+
+    /// Composite health view for uptime monitors.
+    pub fn get_status(&self) {
+        let timelock = self.timelock();
+        let res = ConnectorStatus {
+            maintenance_banner: self.contract.maintenance_banner.clone(),
+            paused_mask: self.get_paused(),
+            contract_version: CONTRACT_VERSION.into(),
+            state_version: STATE_VERSION,
+            scheduled_changes_count: self.contract.scheduled_changes.len() as u64,
+            next_scheduled_change_at: self
+                .contract
+                .scheduled_changes
+                .first()
+                .map(|item| item.effective_at),
+            staged_actions_count: timelock.actions.len() as u64,
+            next_staged_unlock_at: timelock.actions.iter().map(|staged| staged.unlock_at).min(),
+            stuck_submissions_count: self.stuck_submissions().len() as u64,
+            total_supply: self.ft.total_supply,
+            accounts_counter: self.ft.accounts_counter,
+            last_eth_block_height: self.contract.last_eth_block_height,
+            storage_covered: Self::is_storage_covered(),
+            supply_consistent: self.is_supply_consistent(),
+            config_hash: self.contract.config_hash(),
+        }
+        .try_to_vec()
+        .unwrap();
+        sdk::value_return(&res[..]);
+    }
+
+    pub fn get_paused_flags(&self) {
+        let res = self.get_paused().try_to_vec().unwrap();
+        sdk::value_return(&res[..]);
+    }
+
+    pub fn set_paused_flags(&mut self) {
+        self.assert_owner();
+        let args =
+            PauseEthConnectorCallArgs::from(parse_json(&sdk::read_input()).expect(FAILED_PARSE));
+        self.set_paused(args.paused_mask);
+        self.save_contract();
+    }
+
+    pub fn get_accounts_counter(&self) {
+        let res = self.ft.accounts_counter.try_to_vec().unwrap();
+        sdk::value_return(&res[..]);
+    }
+
+    /// Recent deposits and withdrawals of an account, newest first.
+    pub fn get_account_history(&self) {
+        let args =
+            GetAccountHistoryCallArgs::from(parse_json(&sdk::read_input()).expect(FAILED_PARSE));
+        let res: Vec<HistoryEntry> = self
+            .get_history(&args.account_id)
+            .into_iter()
+            .rev()
+            .skip(args.from_index as usize)
+            .take(args.limit as usize)
+            .collect();
+        sdk::value_return(&res.try_to_vec().unwrap()[..]);
+    }
+
+    /// Gas allotted to each downstream call, to diagnose out-of-gas failures.
+    pub fn get_gas_budget(&self) {
+        let res = GasBudget {
+            verify_log_entry: GAS_FOR_VERIFY_LOG_ENTRY,
+            finish_deposit: GAS_FOR_FINISH_DEPOSIT,
+            ft_on_deposit: GAS_FOR_FT_ON_DEPOSIT,
+            finish_engine_deposit: GAS_FOR_FINISH_ENGINE_DEPOSIT,
+            ft_transfer_call: GAS_FOR_FT_TRANSFER_CALL,
+            ft_resolve_transfer: GAS_FOR_RESOLVE_TRANSFER,
+        }
+        .try_to_vec()
+        .unwrap();
+        sdk::value_return(&res[..]);
+    }
+
+    /// Deposit `deposit` calls must attach for storage, the surplus is refunded.
+    pub fn get_deposit_storage_cost(&self) {
+        let cost = self.used_proof_storage_deposit();
+        sdk::value_return(&cost.to_string().as_bytes());
+    }
+
+    /// Work waiting for operators, gathered in a single response for ops automation:
+    /// deposits to retry, fees to claim and failed invariant checks.
+    pub fn get_operator_tasks(&self) {
+        let res = OperatorTasks {
+            stuck_submissions: self.stuck_submissions(),
+            claimable_fees: self.contract.accrued_fees,
+            supply_consistent: self.is_supply_consistent(),
+            storage_covered: Self::is_storage_covered(),
+            shadow_prover_disagreements: self
+                .contract
+                .shadow_prover
+                .as_ref()
+                .map(|shadow_prover| shadow_prover.disagreements)
+                .unwrap_or(0),
+        }
+        .try_to_vec()
+        .unwrap();
+        sdk::value_return(&res[..]);
+    }
+
+    /// Deposits whose prover call failed or ran out of gas, oldest first.
+    /// They can be resubmitted with `deposit` since their proofs are not marked as used.
+    pub fn get_stuck_submissions(&self) {
+        let res = self.stuck_submissions().try_to_vec().unwrap();
+        sdk::value_return(&res[..]);
+    }
+
+    /// Drop a stuck submission once an operator has handled it.
+    pub fn clear_stuck_submission(&mut self) {
+        self.assert_owner();
+        let args = ProofKeyCallArgs::from(parse_json(&sdk::read_input()).expect(FAILED_PARSE));
+        self.remove_stuck_submission(&args.proof_key);
+    }
+
+    /// Highest Ethereum block height seen across accepted deposit proofs.
+    pub fn get_last_eth_block_height(&self) {
+        let res = self.contract.last_eth_block_height.try_to_vec().unwrap();
+        sdk::value_return(&res[..]);
+    }
+
+    /// Paginated list of token holders with their balances.
+    pub fn get_accounts(&self) {
+        let args = GetAccountsCallArgs::from(parse_json(&sdk::read_input()).expect(FAILED_PARSE));
+        let res = self
+            .ft
+            .ft_holders(args.from_index, args.limit.min(MAX_ACCOUNTS_PAGE))
+            .try_to_vec()
+            .unwrap();
+        sdk::value_return(&res[..]);
+    }
+
+    /// Record the code hash of the audited reproducible build, so users can compare it
+    /// with the code hash of this account.
+    pub fn set_code_hash_expectation(&mut self) {
+        self.assert_owner();
+        let args = SetCodeHashExpectationCallArgs::from(
+            parse_json(&sdk::read_input()).expect(FAILED_PARSE),
+        );
+        self.contract.expected_code_hash = Some(args.code_hash);
+        self.save_contract();
+    }
+
+    pub fn get_code_hash_expectation(&self) {
+        let res = CodeHashExpectation {
+            code_hash: self.contract.expected_code_hash,
+            source_commit: SOURCE_COMMIT.map(String::from),
+            contract_version: CONTRACT_VERSION.into(),
+        }
+        .try_to_vec()
+        .unwrap();
+        sdk::value_return(&res[..]);
+    }
+
+    /// Import balances, used proofs, the alias registry and stats exported from another
+    /// connector deployment, so it can be relocated without reopening replay windows.
+    /// A cold start: only accepted once, by a deployment which hasn't minted or recorded
+    /// a proof yet.
+    pub fn migrate(&mut self) {
+        self.assert_owner();
+        self.assert_not_timelocked();
+        // Migrations are only accepted by a deployment with an attested build
+        assert!(
+            self.contract.expected_code_hash.is_some(),
+            "Code hash expectation is not set"
+        );
+        assert!(
+            !sdk::storage_has_key(MIGRATED_KEY),
+            "State was already migrated"
+        );
+        assert!(
+            self.contract.total_minted == 0
+                && self.contract.total_burned == 0
+                && self.used_event_pruning().next_index == 0,
+            "Only a fresh deployment can be migrated"
+        );
+        let data = MigrationInputData::from_input(&sdk::read_input());
+        let mut imported_supply: Balance = 0;
+        for (account_id, balance) in data.accounts {
+            assert_eq!(
+                self.ft.ft_balance_of(account_id.clone()),
+                0,
+                "Account is imported twice"
+            );
+            imported_supply = imported_supply
+                .checked_add(balance)
+                .unwrap_or_else(|| sdk::panic_utf8(ConnectorError::AmountOverflow.as_ref()));
+            self.ft.accounts_insert(account_id, balance);
+        }
+        if let Some(total_supply) = data.total_supply {
+            assert_eq!(
+                total_supply, imported_supply,
+                "Total supply doesn't match the imported balances"
+            );
+        }
+        self.ft.total_supply = imported_supply;
+        // The imported supply is accounted as minted here
+        self.contract.total_minted = imported_supply;
+        if !data.used_proofs.is_empty() {
+            // Imported proofs are recorded under their legacy keys, from unknown heights
+            // unless the export carries one
+            let legacy_height = self
+                .legacy_proof_key_height()
+                .max(self.contract.last_eth_block_height)
+                .max(data.last_eth_block_height.unwrap_or(u64::MAX));
+            sdk::save_contract(LEGACY_PROOF_KEY_HEIGHT_KEY, &legacy_height);
+        }
+        for proof_key in data.used_proofs {
+            self.save_used_event(&proof_key, None, None);
+        }
+        for (alias, account_id) in data.aliases {
+            Self::assert_valid_alias(&alias);
+            // Imported aliases were already resolved by the exporting connector
+            let record = AliasRecord {
+                account_id,
+                eth_block_height: 0,
+            };
+            sdk::save_contract(self.alias_key(&alias).as_str(), &record);
+        }
+        if let Some(height) = data.last_eth_block_height {
+            if height > self.contract.last_eth_block_height {
+                self.contract.last_eth_block_height = height;
+            }
+        }
+        sdk::save_contract(MIGRATED_KEY, &true);
+        self.save_contract();
+        Self::push_schema_log();
+    }
+
+    /// Dry run of `deposit` for relayers: takes the same arguments and reports every
+    /// reason the submission would fail, along with the gas it needs.
+    pub fn preflight(&self) {
+        let mut failures = Vec::new();
+        if self.contract.maintenance_banner.is_some() {
+            failures.push(PreflightFailure::Maintenance);
+        }
+        if self.is_paused(PAUSE_DEPOSIT) {
+            failures.push(PreflightFailure::DepositPaused);
+        }
+        let input = parse_json(&sdk::read_input()).expect(FAILED_PARSE);
+        let with_tx_hash = input.string("eth_tx_hash").is_ok();
+        let versioned_proof = VersionedProof::from(input);
+        let proof = versioned_proof.proof();
+        let mut required_gas = GAS_FOR_VERIFY_LOG_ENTRY + GAS_FOR_FINISH_DEPOSIT;
+        if self.contract.shadow_prover.is_some() {
+            required_gas += GAS_FOR_VERIFY_LOG_ENTRY;
+        }
+        if self.block_confirmations().is_some() {
+            required_gas += GAS_FOR_LAST_BLOCK_NUMBER;
+        }
+
+        let validation = proof.validate().and_then(|_| {
+            if with_tx_hash {
+                proof.validate_receipt()
+            } else {
+                Ok(())
+            }
+        });
+        match validation {
+            Err(err) => failures.push(PreflightFailure::InvalidProof(
+                String::from_utf8_lossy(err.as_ref()).into(),
+            )),
+            Ok(()) => {
+                if self.check_used_proof(proof) {
+                    failures.push(PreflightFailure::ProofAlreadyUsed);
+                }
+                if self.is_pruned_block(proof) {
+                    failures.push(PreflightFailure::ProofTooOld);
+                }
+                match EthDepositedEvent::try_from_log_entry_data(
+                    &proof.log_entry_data,
+                    &self.contract.deposited_event_layout,
+                    &self.contract.event_topics.deposited,
+                ) {
+                    Err(err) => failures.push(PreflightFailure::InvalidEvent(
+                        String::from_utf8_lossy(err.as_ref()).into(),
+                    )),
+                    Ok(event) => {
+                        if !self.is_accepted_custodian(&event.eth_custodian_address, proof) {
+                            failures.push(PreflightFailure::CustodianMismatch);
+                        }
+                        if event.fee >= event.amount {
+                            failures.push(PreflightFailure::FeeExceedsAmount);
+                        }
+                        match self.try_resolve_recipient(event.recipient, proof) {
+                            Err(failure) => failures.push(failure),
+                            Ok(new_owner_id) => {
+                                if self.is_engine_deposit(&new_owner_id, &event.message) {
+                                    required_gas +=
+                                        GAS_FOR_FT_ON_DEPOSIT + GAS_FOR_FINISH_ENGINE_DEPOSIT;
+                                } else if event.message.is_some() {
+                                    required_gas += GAS_FOR_FT_TRANSFER_CALL;
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        let res = PreflightReport {
+            failures,
+            required_gas,
+        }
+        .try_to_vec()
+        .unwrap();
+        sdk::value_return(&res[..]);
+    }
+
+    /// Receipt of a finalized deposit, by its trace id or by the key of its proof, see
+    /// `get_proof_key`. There is no lookup by Ethereum transaction hash: the proof doesn't
+    /// commit to it, so a submitter could claim the hash of another deposit.
+    pub fn find_deposit(&self) {
+        let args = FindDepositCallArgs::from(parse_json(&sdk::read_input()).expect(FAILED_PARSE));
+        let trace_id = match args {
+            FindDepositCallArgs::TraceId(trace_id) => Some(trace_id),
+            FindDepositCallArgs::ProofKey(proof_key) => self.deposit_proof_trace_id(&proof_key),
+        };
+        let res = trace_id
+            .and_then(|trace_id| sdk::read_storage(self.deposit_receipt_key(&trace_id).as_bytes()))
+            .map(|data| DepositReceipt::try_from_slice(&data[..]).unwrap())
+            .try_to_vec()
+            .unwrap();
+        sdk::value_return(&res[..]);
+    }
+
+    /// Key of the given proof, under which its use is recorded and its deposit is found.
+    pub fn get_proof_key(&self) {
+        let proof = VersionedProof::from(parse_json(&sdk::read_input()).expect(FAILED_PARSE));
+        let res = proof.proof().get_key().try_to_vec().unwrap();
+        sdk::value_return(&res[..]);
+    }
+
+    /// Whether the given proof was already used, to check for replay before submitting it.
+    pub fn is_used_proof(&self) {
+        let proof = VersionedProof::from(parse_json(&sdk::read_input()).expect(FAILED_PARSE));
+        let res = self.check_used_proof(proof.proof()).try_to_vec().unwrap();
+        sdk::value_return(&res[..]);
+    }
+
+    /// Delete the records of used proofs from Ethereum blocks below `before_height`, at
+    /// most `limit` of them in the order they were recorded, and send the freed storage
+    /// to `refund_account_id`, the owner by default. `before_height` must be at least the
+    /// finality horizon behind the highest accepted block. Proofs from blocks below the
+    /// highest `before_height` so far are rejected from then on, so pruned proofs can't
+    /// be replayed. Returns the number of deleted records.
+    pub fn prune_used_events(&mut self) {
+        self.assert_owner();
+        let args =
+            PruneUsedEventsCallArgs::from(parse_json(&sdk::read_input()).expect(FAILED_PARSE));
+        let mut pruning = self.used_event_pruning();
+        assert!(
+            args.before_height.saturating_add(pruning.horizon_blocks)
+                <= self.contract.last_eth_block_height,
+            "Can't prune within the finality horizon"
+        );
+        let initial_storage = sdk::storage_usage();
+        let mut pruned = 0u64;
+        let mut steps = 0u64;
+        // Records arrive roughly by height, stop at the first one which is still kept
+        while steps < args.limit && pruning.pruned_index < pruning.next_index {
+            steps += 1;
+            let index_key = self.used_event_index_key(pruning.pruned_index);
+            let entry = match sdk::read_storage(index_key.as_bytes()) {
+                Some(data) => UsedEventIndexEntry::try_from_slice(&data[..]).unwrap(),
+                // Removed by a rolled back engine deposit
+                None => {
+                    pruning.pruned_index += 1;
+                    continue;
+                }
+            };
+            if entry.eth_block_height >= args.before_height {
+                break;
+            }
+            sdk::remove_storage(self.used_event_key(&entry.proof_key).as_bytes());
+            sdk::remove_storage(index_key.as_bytes());
+            pruning.pruned_index += 1;
+            pruned += 1;
+        }
+        pruning.pruned_before_height = pruning.pruned_before_height.max(args.before_height);
+        self.save_used_event_pruning(&pruning);
+
+        let refund = Balance::from(initial_storage.saturating_sub(sdk::storage_usage()))
+            * sdk::STORAGE_PRICE_PER_BYTE;
+        if refund > 0 {
+            let refund_account_id = args
+                .refund_account_id
+                .unwrap_or_else(|| self.get_owner().clone());
+            let promise0 = sdk::promise_batch_create(refund_account_id);
+            sdk::promise_batch_action_transfer(promise0, refund);
+        }
+        sdk::value_return(&pruned.try_to_vec().unwrap()[..]);
+    }
+
+    /// Set how many Ethereum blocks a proof must be behind the highest accepted block
+    /// before `prune_used_events` can delete it.
+    pub fn set_prune_horizon(&mut self) {
+        self.assert_owner();
+        self.assert_not_timelocked();
+        let args =
+            SetPruneHorizonCallArgs::from(parse_json(&sdk::read_input()).expect(FAILED_PARSE));
+        assert!(
+            args.horizon_blocks >= MIN_PRUNE_HORIZON_BLOCKS,
+            "Pruning horizon is too short"
+        );
+        let mut pruning = self.used_event_pruning();
+        pruning.horizon_blocks = args.horizon_blocks;
+        self.save_used_event_pruning(&pruning);
+    }
+
+    pub fn get_used_event_pruning(&self) {
+        let res = self.used_event_pruning().try_to_vec().unwrap();
+        sdk::value_return(&res[..]);
+    }
+
+    /// Same as `is_used_proof`, by the key of the proof.
+    pub fn is_used_event(&self) {
+        let args = ProofKeyCallArgs::from(parse_json(&sdk::read_input()).expect(FAILED_PARSE));
+        let res = self.check_used_event(&args.proof_key).try_to_vec().unwrap();
+        sdk::value_return(&res[..]);
+    }
+
+    /// Account which submitted the proof, if it was used by a deposit on this contract.
+    pub fn get_proof_submitter(&self) {
+        let args = ProofKeyCallArgs::from(parse_json(&sdk::read_input()).expect(FAILED_PARSE));
+        let res = self
+            .used_event_record(&args.proof_key)
+            .and_then(|record| record.submitter_id)
+            .try_to_vec()
+            .unwrap();
+        sdk::value_return(&res[..]);
+    }
+
+    /// Prover which verified the proof, `None` for proofs recorded before provers were tracked.
+    pub fn get_proof_prover(&self) {
+        let args = ProofKeyCallArgs::from(parse_json(&sdk::read_input()).expect(FAILED_PARSE));
+        let res = self
+            .used_event_record(&args.proof_key)
+            .and_then(|record| record.prover_account)
+            .try_to_vec()
+            .unwrap();
+        sdk::value_return(&res[..]);
+    }
+
+    /// Mark an accepted proof as revoked after its Ethereum block was orphaned by a reorg,
+    /// burning up to `clawback_amount` of the tokens minted for it where they are still held.
+    /// The proof stays used, so it can't be submitted again.
+    pub fn revoke_proof(&mut self) {
+        self.assert_owner();
+        self.assert_not_timelocked();
+        let args = RevokeProofCallArgs::from(parse_json(&sdk::read_input()).expect(FAILED_PARSE));
+        self.apply_unstaged_action(AdminAction::RevokeProof(args));
+    }
+
+    fn apply_revoke_proof(&mut self, args: RevokeProofCallArgs) {
+        let key = self.revoked_proof_key(&args.proof_key);
+        let clawed_back = match &args.clawback_account_id {
+            Some(account_id) => {
+                let amount = self
+                    .ft
+                    .ft_balance_of(account_id.clone())
+                    .min(args.clawback_amount);
+                if amount > 0 {
+                    self.burn(account_id.clone(), amount);
+                }
+                amount
+            }
+            None => 0,
+        };
+        events::emit_proof_revoked(&args.proof_key, &args.clawback_account_id, clawed_back);
+        let revoked = RevokedProof {
+            evidence: args.evidence,
+            clawback_account_id: args.clawback_account_id,
+            clawed_back,
+            timestamp: sdk::block_timestamp(),
+        };
+        sdk::save_contract(key.as_str(), &revoked);
+    }
+
+    pub fn get_revoked_proof(&self) {
+        let args = ProofKeyCallArgs::from(parse_json(&sdk::read_input()).expect(FAILED_PARSE));
+        let res = sdk::read_storage(self.revoked_proof_key(&args.proof_key).as_bytes())
+            .map(|data| RevokedProof::try_from_slice(&data[..]).unwrap())
+            .try_to_vec()
+            .unwrap();
+        sdk::value_return(&res[..]);
+    }
+
+    /// For each proof key, return `true` if it has not been used yet.
+    /// Lets relayers check a batch of candidate events in one view call.
+    pub fn filter_unused_proof_keys(&self) {
+        let args =
+            FilterProofKeysCallArgs::from(parse_json(&sdk::read_input()).expect(FAILED_PARSE));
+        let res: Vec<bool> = args
+            .keys
+            .iter()
+            .map(|key| !self.check_used_event(key))
+            .collect();
+        sdk::value_return(&res.try_to_vec().unwrap()[..]);
+    }
+
+    /// Check that the state imported by `migrate` matches the given data.
+    pub fn check_migration_correctness(&self) {
+        let data = MigrationInputData::from_input(&sdk::read_input());
+        let mut not_exist = Vec::new();
+        let mut wrong_amount = Vec::new();
+        for (account_id, balance) in data.accounts {
+            match self.ft.accounts_get(account_id.clone()) {
+                Some(value) => {
+                    let stored = u128::try_from_slice(&value[..]).unwrap();
+                    if stored != balance {
+                        wrong_amount.push((account_id, stored));
+                    }
+                }
+                None => not_exist.push(account_id),
+            }
+        }
+        let missing_proofs: Vec<String> = data
+            .used_proofs
+            .into_iter()
+            .filter(|key| !self.check_used_event(key))
+            .collect();
+        let wrong_aliases: Vec<String> = data
+            .aliases
+            .into_iter()
+            .filter(|(alias, account_id)| {
+                self.get_alias(alias)
+                    .map(|record| record.account_id)
+                    .as_ref()
+                    != Some(account_id)
+            })
+            .map(|(alias, _)| alias)
+            .collect();
+
+        let res = if !not_exist.is_empty() {
+            MigrationCheckResult::AccountNotExist(not_exist)
+        } else if !wrong_amount.is_empty() {
+            MigrationCheckResult::AccountAmount(wrong_amount)
+        } else if data
+            .total_supply
+            .map_or(false, |total_supply| total_supply != self.ft.total_supply)
+        {
+            MigrationCheckResult::TotalSupply(self.ft.total_supply)
+        } else if !missing_proofs.is_empty() {
+            MigrationCheckResult::Proof(missing_proofs)
+        } else if !wrong_aliases.is_empty() {
+            MigrationCheckResult::Alias(wrong_aliases)
+        } else if data
+            .last_eth_block_height
+            .map_or(false, |height| height > self.contract.last_eth_block_height)
+        {
+            MigrationCheckResult::LastEthBlockHeight(self.contract.last_eth_block_height)
+        } else {
+            MigrationCheckResult::Success
+        };
+        sdk::value_return(&res.try_to_vec().unwrap()[..]);
+    }
+
+    /// Compare minted-minus-burned totals with the token total supply, emitting a
+    /// `supply_discrepancy` event when they diverge to catch accounting drift early.
+    pub fn reconcile(&self) {
+        let total_minted = self.contract.total_minted;
+        let total_burned = self.contract.total_burned;
+        let total_supply = self.ft.total_supply;
+        let consistent = self.is_supply_consistent();
+        if !consistent {
+            events::emit_supply_discrepancy(total_minted, total_burned, total_supply);
+        }
+        let res = ReconcileReport {
+            total_minted,
+            total_burned,
+            total_supply,
+            consistent,
+        }
+        .try_to_vec()
+        .unwrap();
+        sdk::value_return(&res[..]);
+    }
+
+    pub fn ft_total_supply(&self) {
+        let total_supply = self.ft.ft_total_supply();
+        sdk::value_return(&total_supply.to_string().as_bytes());
+        #[cfg(feature = "log")]
+        sdk::log(format!("Total supply: {}", total_supply));
+    }
+
+    pub fn ft_metadata(&self) {
+        sdk::value_return(self.ft.ft_metadata().as_bytes());
+    }
+
+    pub fn ft_balance_of(&self) {
+        let args = BalanceOfCallArgs::from(parse_json(&sdk::read_input()).expect(FAILED_PARSE));
+        let balance = self.ft.ft_balance_of(args.account_id.clone());
+        sdk::value_return(&balance.to_string().as_bytes());
+        #[cfg(feature = "log")]
+        sdk::log(format!("Balance [{}]: {}", args.account_id, balance));
+    }
+
+    pub fn ft_transfer(&mut self) {
+        self.assert_not_in_maintenance();
+        let args: TransferCallArgs =
+            TransferCallArgs::from(parse_json(&sdk::read_input()).expect(FAILED_PARSE));
+
+        self.ft
+            .ft_transfer(args.receiver_id.clone(), args.amount, args.memo.clone());
+        self.save_contract();
+        #[cfg(feature = "log")]
+        sdk::log(format!(
+            "Transfer amount {} to {} success with memo: {:?}",
+            args.amount, args.receiver_id, args.memo
+        ));
+    }
+
+    pub fn ft_resolve_transfer(&mut self) {
+        sdk::assert_private_call();
+        let args: ResolveTransferCallArgs =
+            ResolveTransferCallArgs::try_from_slice(&sdk::read_input()).unwrap();
+        let amount = self.ft.ft_resolve_transfer(
+            args.sender_id.clone(),
+            args.receiver_id.clone(),
+            args.amount,
+        );
+        // `ft_resolve_transfer` can changed `total_supply` so we should save contract
+        self.save_contract();
+        sdk::value_return(&amount.to_string().as_bytes());
+        #[cfg(feature = "log")]
+        sdk::log(format!(
+            "Resolve transfer of {} from {} to {} success",
+            args.amount, args.sender_id, args.receiver_id
+        ));
+    }
+
+    pub fn ft_transfer_call(&mut self) {
+        self.assert_not_in_maintenance();
+        let args: TransferCallCallArgs =
+            TransferCallCallArgs::from(parse_json(&sdk::read_input()).expect(FAILED_PARSE));
+        #[cfg(feature = "log")]
+        sdk::log(format!(
+            "Transfer call to {} amount {}",
+            args.receiver_id, args.amount,
+        ));
+
+        self.ft.ft_transfer_call(
+            args.receiver_id.clone(),
+            args.amount,
+            args.memo.clone(),
+            args.msg.clone(),
+        );
+    }
+
+    pub fn storage_deposit(&mut self) {
+        self.assert_not_in_maintenance();
+        let args: StorageDepositCallArgs =
+            StorageDepositCallArgs::from(parse_json(&sdk::read_input()).expect(FAILED_PARSE));
+        let res = self
+            .ft
+            .storage_deposit(args.account_id, args.registration_only)
+            .try_to_vec()
+            .unwrap();
+        self.save_contract();
+        sdk::value_return(&res[..]);
+    }
+
+    pub fn storage_withdraw(&mut self) {
+        self.assert_not_in_maintenance();
         let args: StorageWithdrawCallArgs =
             StorageWithdrawCallArgs::from(parse_json(&sdk::read_input()).expect(FAILED_PARSE));
         let res = self.ft.storage_withdraw(args.amount).try_to_vec().unwrap();
@@ -286,20 +2625,720 @@ impl EthConnectorContract {
         sdk::value_return(&res[..]);
     }
 
+    fn assert_not_frozen(&self, flag: FrozenMask) {
+        assert!(self.contract.frozen_mask & flag == 0, "Parameter is frozen");
+    }
+
+    fn assert_in_future(effective_at: u64) {
+        assert!(
+            effective_at > sdk::block_timestamp(),
+            "Scheduled change must take effect in the future"
+        );
+    }
+
+    /// `ft_resolve_transfer` is not guarded, so in-flight transfer calls can still be settled.
+    fn assert_not_in_maintenance(&self) {
+        if self.contract.maintenance_banner.is_some() {
+            sdk::panic_utf8(ConnectorError::Maintenance.as_ref());
+        }
+    }
+
+    /// Keep `amount` of the attached deposit and refund the surplus to the predecessor.
+    fn charge_storage_deposit(amount: Balance) {
+        let attached_deposit = sdk::attached_deposit();
+        if attached_deposit < amount {
+            sdk::panic_utf8(ConnectorError::InsufficientStorageDeposit.as_ref());
+        }
+        if attached_deposit > amount {
+            let promise0 = sdk::promise_batch_create(sdk::predecessor_account_id());
+            sdk::promise_batch_action_transfer(promise0, attached_deposit - amount);
+        }
+    }
+
+    fn schema_log() -> Vec<SchemaLogEntry> {
+        sdk::read_storage(storage_key(KeyPrefix::SchemaLog, &[]).as_bytes())
+            .map(|data| Vec::<SchemaLogEntry>::try_from_slice(&data[..]).unwrap())
+            .unwrap_or_default()
+    }
+
+    /// Append the running versions to the schema log, it is never truncated.
+    fn push_schema_log() {
+        let mut log = Self::schema_log();
+        log.push(SchemaLogEntry {
+            contract_version: CONTRACT_VERSION.into(),
+            state_version: STATE_VERSION,
+            block_height: sdk::block_index(),
+        });
+        sdk::save_contract(storage_key(KeyPrefix::SchemaLog, &[]).as_str(), &log);
+    }
+
+    fn custodian_history_key(&self) -> String {
+        storage_key(KeyPrefix::CustodianHistory, &[])
+    }
+
+    fn custodian_history(&self) -> Vec<RetiredCustodian> {
+        sdk::read_storage(self.custodian_history_key().as_bytes())
+            .map(|data| Vec::<RetiredCustodian>::try_from_slice(&data[..]).unwrap())
+            .unwrap_or_default()
+    }
+
+    fn replace_eth_custodian(&mut self, args: SetEthCustodianAddressCallArgs) {
+        assert!(
+            args.eth_custodian_address != self.contract.eth_custodian_address,
+            "Custodian address is unchanged"
+        );
+        let mut history = self.custodian_history();
+        history.push(RetiredCustodian {
+            eth_custodian_address: self.contract.eth_custodian_address,
+            retired_at: sdk::block_index(),
+            accepted_until_eth_block: args.grace_until_eth_block.or_else(|| {
+                args.grace_blocks
+                    .map(|blocks| self.contract.last_eth_block_height + blocks)
+            }),
+        });
+        sdk::save_contract(self.custodian_history_key().as_str(), &history);
+        self.contract.eth_custodian_address = args.eth_custodian_address;
+    }
+
+    /// Whether deposits to `eth_custodian_address` are minted: the current custodian,
+    /// or a replaced one in its grace period for the Ethereum block of the proof.
+    fn is_accepted_custodian(&self, eth_custodian_address: &EthAddress, proof: &Proof) -> bool {
+        if *eth_custodian_address == self.contract.eth_custodian_address {
+            return true;
+        }
+        let accepted_until = self
+            .custodian_history()
+            .into_iter()
+            .filter(|item| item.eth_custodian_address == *eth_custodian_address)
+            .filter_map(|item| item.accepted_until_eth_block)
+            .max();
+        match (accepted_until, proof.block_number()) {
+            (Some(until), Ok(height)) => height <= until,
+            _ => false,
+        }
+    }
+
+    fn deferred_deposit_key(&self, proof_key: &str) -> String {
+        storage_key(KeyPrefix::DeferredDeposit, &[proof_key])
+    }
+
+    fn timelock_key(&self) -> String {
+        storage_key(KeyPrefix::Timelock, &[])
+    }
+
+    fn timelock(&self) -> Timelock {
+        sdk::read_storage(self.timelock_key().as_bytes())
+            .map(|data| Timelock::try_from_slice(&data[..]).unwrap())
+            .unwrap_or_default()
+    }
+
+    fn save_timelock(&self, timelock: &Timelock) {
+        sdk::save_contract(self.timelock_key().as_str(), timelock);
+    }
+
+    fn assert_not_timelocked(&self) {
+        assert!(self.timelock().delay == 0, "Timelocked, use stage_action");
+    }
+
+    /// Same checks as the setters of the changed parameters.
+    fn validate_admin_action(&self, action: &AdminAction) {
+        match action {
+            AdminAction::SetEthCustodianAddress(args) => {
+                self.assert_not_frozen(FREEZE_CUSTODIAN);
+                assert!(
+                    args.eth_custodian_address != EthAddress::default(),
+                    "Custodian address is zero"
+                );
+                assert!(
+                    args.grace_blocks.is_none() || args.grace_until_eth_block.is_none(),
+                    "Only one of grace_blocks and grace_until_eth_block can be set"
+                );
+            }
+            AdminAction::SetProverAccount(prover_account) => {
+                self.assert_not_frozen(FREEZE_PROVER);
+                assert!(
+                    Self::is_valid_account_id(prover_account),
+                    "Invalid prover account"
+                );
+            }
+            AdminAction::SetRelayerFeeShare(relayer_fee_share_bps) => {
+                self.assert_not_frozen(FREEZE_FEES);
+                assert!(
+                    *relayer_fee_share_bps <= BPS_DENOMINATOR,
+                    "Relayer fee share exceeds 100%"
+                );
+            }
+            AdminAction::SetDepositFee(deposit_fee_bps) => {
+                self.assert_not_frozen(FREEZE_FEES);
+                assert!(
+                    *deposit_fee_bps <= MAX_DEPOSIT_FEE_BPS,
+                    "Deposit fee exceeds the maximum"
+                );
+            }
+            AdminAction::SetWithdrawFee {
+                withdraw_fee_bps, ..
+            } => {
+                self.assert_not_frozen(FREEZE_FEES);
+                assert!(
+                    *withdraw_fee_bps <= MAX_WITHDRAW_FEE_BPS,
+                    "Withdraw fee exceeds the maximum"
+                );
+            }
+            AdminAction::SetDepositedEventLayout(layout) => {
+                self.assert_not_frozen(FREEZE_EVENT_FORMAT);
+                assert_eq!(
+                    layout.indexed.len(),
+                    4,
+                    "Layout should describe all 4 params"
+                );
+                // An indexed string is only stored as its hash, so the recipient can't be recovered
+                assert!(!layout.indexed[1], "Recipient param can't be indexed");
+            }
+            AdminAction::SetEngineAccount(_) => self.assert_not_frozen(FREEZE_ENGINE_ACCOUNT),
+            AdminAction::ScheduleDepositedEventTopic(args) => {
+                self.assert_not_frozen(FREEZE_EVENT_FORMAT);
+                Self::assert_in_future(args.effective_at);
+            }
+            AdminAction::RevokeProof(args) => {
+                assert!(
+                    self.check_used_event(&args.proof_key),
+                    "Only accepted proofs can be revoked"
+                );
+                assert!(
+                    !sdk::storage_has_key(self.revoked_proof_key(&args.proof_key).as_str()),
+                    "Proof is already revoked"
+                );
+            }
+            AdminAction::Upgrade(_) | AdminAction::SetTimelockDelay(_) => {}
+        }
+    }
+
+    /// Validate and apply an admin action called directly, while the timelock is off.
+    fn apply_unstaged_action(&mut self, action: AdminAction) {
+        self.validate_admin_action(&action);
+        self.apply_admin_action(action);
+        self.save_contract();
+    }
+
+    fn apply_admin_action(&mut self, action: AdminAction) {
+        match action {
+            AdminAction::SetEthCustodianAddress(args) => self.replace_eth_custodian(args),
+            AdminAction::SetProverAccount(prover_account) => {
+                self.contract.prover_account = prover_account
+            }
+            AdminAction::SetRelayerFeeShare(relayer_fee_share_bps) => {
+                self.contract.relayer_fee_share_bps = relayer_fee_share_bps
+            }
+            AdminAction::SetDepositFee(deposit_fee_bps) => {
+                self.contract.deposit_fee_bps = deposit_fee_bps
+            }
+            AdminAction::SetWithdrawFee {
+                withdraw_fee_bps,
+                min_withdraw_amount,
+            } => {
+                self.contract.withdraw_fee_bps = withdraw_fee_bps;
+                self.contract.min_withdraw_amount = min_withdraw_amount;
+            }
+            AdminAction::SetDepositedEventLayout(layout) => {
+                self.contract.deposited_event_layout = layout
+            }
+            AdminAction::SetEngineAccount(engine_account) => {
+                self.contract.engine_account = engine_account
+            }
+            AdminAction::ScheduleDepositedEventTopic(args) => {
+                self.contract.schedule_change(ScheduledParamChange {
+                    effective_at: args.effective_at,
+                    change: ParamChange::DepositedEventTopic(args.topic),
+                })
+            }
+            AdminAction::RevokeProof(args) => self.apply_revoke_proof(args),
+            // Handled by `upgrade` and `execute_action`
+            AdminAction::Upgrade(_) | AdminAction::SetTimelockDelay(_) => {}
+        }
+    }
+
+    /// Fail the call for the `guard` class as the refusal policy says: panic with `err`,
+    /// or refund the attached deposit and return a `GuardRefusal`.
+    fn refuse(&self, guard: RefusalMask, err: &[u8]) {
+        if self.refusal_mask() & guard == 0 {
+            sdk::panic_utf8(err);
+        }
+        Self::refund_attached_deposit();
+        let res = GuardRefusal {
+            guard,
+            error: String::from_utf8(err.to_vec()).unwrap(),
+        }
+        .try_to_vec()
+        .unwrap();
+        sdk::value_return(&res[..]);
+    }
+
+    fn refusal_policy_key(&self) -> String {
+        storage_key(KeyPrefix::RefusalPolicy, &[])
+    }
+
+    fn refusal_mask(&self) -> RefusalMask {
+        sdk::read_storage(self.refusal_policy_key().as_bytes())
+            .map(|data| RefusalMask::try_from_slice(&data[..]).unwrap())
+            .unwrap_or(0)
+    }
+
+    /// Return the attached deposit of a call which succeeds without doing any work.
+    /// Calls rejected with a panic are refunded by the runtime.
+    fn refund_attached_deposit() {
+        let amount = sdk::attached_deposit();
+        if amount > 0 {
+            let promise0 = sdk::promise_batch_create(sdk::predecessor_account_id());
+            sdk::promise_batch_action_transfer(promise0, amount);
+        }
+    }
+
     fn save_contract(&mut self) {
         sdk::save_contract(CONTRACT_NAME_KEY, &self.contract);
         sdk::save_contract(CONTRACT_FT_KEY, &self.ft);
     }
 
     fn used_event_key(&self, key: &str) -> String {
-        [CONTRACT_NAME_KEY, "used-event", key].join(".")
+        storage_key(KeyPrefix::UsedEvent, &[key])
     }
 
-    fn save_used_event(&self, key: &str) {
-        sdk::save_contract(self.used_event_key(key).as_str(), &0u8);
+    fn save_used_event(
+        &self,
+        key: &str,
+        submitter_id: Option<&AccountId>,
+        prover_account: Option<&AccountId>,
+    ) {
+        let record = UsedEventRecord {
+            submitter_id: submitter_id.cloned(),
+            prover_account: prover_account.cloned(),
+        };
+        sdk::save_contract(self.used_event_key(key).as_str(), &record);
+    }
+
+    /// Records predating submitter attribution hold `0u8`, later ones without a prover
+    /// hold a borsh `Option<AccountId>`; both are too short to decode as `UsedEventRecord`.
+    fn used_event_record(&self, key: &str) -> Option<UsedEventRecord> {
+        sdk::read_storage(self.used_event_key(key).as_bytes()).map(|data| {
+            UsedEventRecord::try_from_slice(&data[..]).unwrap_or_else(|_| UsedEventRecord {
+                submitter_id: Option::<AccountId>::try_from_slice(&data[..]).unwrap(),
+                prover_account: None,
+            })
+        })
+    }
+
+    fn used_event_index_key(&self, index: u64) -> String {
+        storage_key(KeyPrefix::UsedEventIndex, &[&index.to_string()])
+    }
+
+    fn used_event_pruning(&self) -> UsedEventPruning {
+        sdk::read_storage(storage_key(KeyPrefix::UsedEventIndex, &[]).as_bytes())
+            .map(|data| UsedEventPruning::try_from_slice(&data[..]).unwrap())
+            .unwrap_or_default()
+    }
+
+    fn save_used_event_pruning(&self, pruning: &UsedEventPruning) {
+        sdk::save_contract(
+            storage_key(KeyPrefix::UsedEventIndex, &[]).as_str(),
+            pruning,
+        );
+    }
+
+    /// Whether used proofs of the proof block may have been pruned.
+    fn is_pruned_block(&self, proof: &Proof) -> bool {
+        let pruned_before_height = self.used_event_pruning().pruned_before_height;
+        proof
+            .block_number()
+            .map_or(false, |height| height < pruned_before_height)
+    }
+
+    /// Whether the proof was used, under its key or its legacy key.
+    fn check_used_proof(&self, proof: &Proof) -> bool {
+        self.check_used_event(&proof.get_key()) || self.check_used_legacy_key(proof)
+    }
+
+    /// Only proofs up to the legacy key height can be recorded under their legacy key:
+    /// none on deployments initialized with compact keys, any on upgraded ones.
+    fn check_used_legacy_key(&self, proof: &Proof) -> bool {
+        let may_be_legacy = proof
+            .block_number()
+            .map_or(true, |height| height <= self.legacy_proof_key_height());
+        may_be_legacy && self.check_used_event(&proof.legacy_key())
+    }
+
+    fn legacy_proof_key_height(&self) -> u64 {
+        sdk::read_storage(LEGACY_PROOF_KEY_HEIGHT_KEY.as_bytes())
+            .map(|data| u64::try_from_slice(&data[..]).unwrap())
+            .unwrap_or(u64::MAX)
     }
 
     fn check_used_event(&self, key: &str) -> bool {
         sdk::storage_has_key(self.used_event_key(key).as_str())
     }
+
+    fn revoked_proof_key(&self, proof_key: &str) -> String {
+        storage_key(KeyPrefix::RevokedProof, &[proof_key])
+    }
+
+    /// Checks of a deposit event done before calling the prover.
+    fn validate_deposit_event(
+        &self,
+        event: &EthDepositedEvent,
+        proof: &Proof,
+    ) -> Result<(), ConnectorError> {
+        if !self.is_accepted_custodian(&event.eth_custodian_address, proof) {
+            #[cfg(feature = "log")]
+            sdk::log(format!(
+                "Event's address {} does not match custodian address {}",
+                hex::encode(&event.eth_custodian_address.0),
+                hex::encode(&self.contract.eth_custodian_address.0),
+            ));
+            return Err(ConnectorError::WrongEventAddress);
+        }
+        if event.fee >= event.amount {
+            return Err(ConnectorError::NotEnoughBalanceForFee);
+        }
+        if self.is_pruned_block(proof) {
+            return Err(ConnectorError::ProofTooOld);
+        }
+        Ok(())
+    }
+
+    /// Identifier of a deposit submission, followed across its callbacks, receipt and events.
+    fn trace_id(proof: &Proof) -> String {
+        let mut data = proof.hash().to_vec();
+        data.extend(sdk::predecessor_account_id().into_bytes());
+        data.extend(sdk::block_index().to_le_bytes().iter());
+        data.extend(sdk::block_timestamp().to_le_bytes().iter());
+        hex::encode(&sdk::sha256(&data[..])[..16])
+    }
+
+    fn deposit_receipt_key(&self, trace_id: &str) -> String {
+        storage_key(KeyPrefix::DepositTrace, &[trace_id])
+    }
+
+    /// Points a proof key to the trace id of its deposit.
+    fn deposit_proof_key(&self, proof_key: &str) -> String {
+        storage_key(KeyPrefix::DepositProof, &[proof_key])
+    }
+
+    fn deposit_proof_trace_id(&self, proof_key: &str) -> Option<String> {
+        sdk::read_storage(self.deposit_proof_key(proof_key).as_bytes())
+            .map(|data| String::try_from_slice(&data[..]).unwrap())
+    }
+
+    fn history_key(&self, account_id: &str) -> String {
+        storage_key(KeyPrefix::History, &[account_id])
+    }
+
+    fn get_history(&self, account_id: &str) -> Vec<HistoryEntry> {
+        sdk::read_storage(self.history_key(account_id).as_bytes())
+            .map(|data| Vec::<HistoryEntry>::try_from_slice(&data[..]).unwrap())
+            .unwrap_or_default()
+    }
+
+    /// Remove the latest deposit entry from `sender` made at `timestamp`, if it wasn't
+    /// dropped from the history yet.
+    fn remove_history(&self, account_id: &str, sender: &str, timestamp: u64) {
+        let mut history = self.get_history(account_id);
+        if let Some(index) = history.iter().rposition(|entry| {
+            entry.kind == HistoryKind::Deposit
+                && entry.eth_address == sender
+                && entry.timestamp == timestamp
+        }) {
+            history.remove(index);
+            sdk::save_contract(self.history_key(account_id).as_str(), &history);
+        }
+    }
+
+    /// Append to the account history, dropping the oldest entry when it is full.
+    fn push_history(&self, account_id: &str, entry: HistoryEntry) {
+        let mut history = self.get_history(account_id);
+        if history.len() >= MAX_ACCOUNT_HISTORY {
+            history.remove(0);
+        }
+        history.push(entry);
+        sdk::save_contract(self.history_key(account_id).as_str(), &history);
+    }
+
+    fn stuck_submissions_key(&self) -> String {
+        storage_key(KeyPrefix::Stuck, &[])
+    }
+
+    fn stuck_submissions(&self) -> Vec<StuckSubmission> {
+        sdk::read_storage(self.stuck_submissions_key().as_bytes())
+            .map(|data| Vec::<StuckSubmission>::try_from_slice(&data[..]).unwrap())
+            .unwrap_or_default()
+    }
+
+    fn save_stuck_submissions(&self, submissions: Vec<StuckSubmission>) {
+        if submissions.is_empty() {
+            sdk::remove_storage(self.stuck_submissions_key().as_bytes());
+        } else {
+            sdk::save_contract(self.stuck_submissions_key().as_str(), &submissions);
+        }
+    }
+
+    /// Count the shadow prover result against the primary one. Disagreements on proofs
+    /// the primary prover rejects aren't kept, the rejection reverts the callback.
+    fn record_shadow_verification(
+        &mut self,
+        data: &FinishDepositCallArgs,
+        verified: bool,
+        shadow_result: PromiseResult,
+    ) {
+        let shadow_prover = match self.contract.shadow_prover.as_mut() {
+            Some(shadow_prover) => shadow_prover,
+            // Unset while the prover calls were in flight
+            None => return,
+        };
+        let shadow_verified = match shadow_result {
+            PromiseResult::Successful(x) => bool::try_from_slice(&x).ok(),
+            _ => None,
+        };
+        match shadow_verified {
+            Some(shadow_verified) if shadow_verified == verified => shadow_prover.agreements += 1,
+            Some(_) => shadow_prover.disagreements += 1,
+            None => shadow_prover.failures += 1,
+        }
+        if shadow_verified != Some(verified) {
+            events::emit_prover_disagreement(
+                &data.proof.proof().get_key(),
+                &shadow_prover.account_id,
+                verified,
+                shadow_verified,
+            );
+        }
+    }
+
+    /// Whether tokens minted minus tokens burned add up to the total supply.
+    fn is_supply_consistent(&self) -> bool {
+        self.contract
+            .total_minted
+            .checked_sub(self.contract.total_burned)
+            == Some(self.ft.total_supply)
+    }
+
+    /// Whether the account balance covers the storage staked by the contract.
+    fn is_storage_covered() -> bool {
+        let storage_cost = Balance::from(sdk::storage_usage()) * sdk::STORAGE_PRICE_PER_BYTE;
+        sdk::account_balance() >= storage_cost
+    }
+
+    /// Keep a deposit whose prover call didn't succeed, dropping the oldest one when full.
+    fn record_stuck_submission(&self, data: &FinishDepositCallArgs) {
+        let proof_key = data.proof.proof().get_key();
+        let mut submissions = self.stuck_submissions();
+        submissions.retain(|item| item.proof_key != proof_key);
+        if submissions.len() >= MAX_STUCK_SUBMISSIONS {
+            submissions.remove(0);
+        }
+        events::emit_deposit_stuck(&proof_key, &data.relayer_id, &data.trace_id);
+        submissions.push(StuckSubmission {
+            proof_key,
+            new_owner_id: data.new_owner_id.clone(),
+            amount: data.amount,
+            relayer_id: data.relayer_id.clone(),
+            timestamp: sdk::block_timestamp(),
+            trace_id: data.trace_id.clone(),
+        });
+        self.save_stuck_submissions(submissions);
+    }
+
+    fn remove_stuck_submission(&self, proof_key: &str) {
+        let mut submissions = self.stuck_submissions();
+        let len = submissions.len();
+        submissions.retain(|item| item.proof_key != proof_key);
+        if submissions.len() != len {
+            self.save_stuck_submissions(submissions);
+        }
+    }
+
+    fn blocked_eth_recipient_key(&self, eth_address: &EthAddress) -> String {
+        let address = hex::encode(eth_address.0);
+        storage_key(KeyPrefix::BlockedEthRecipient, &[address.as_str()])
+    }
+
+    fn is_blocked_eth_recipient(&self, eth_address: &EthAddress) -> bool {
+        sdk::storage_has_key(self.blocked_eth_recipient_key(eth_address).as_str())
+    }
+
+    fn withdraw_allowance_key(&self, owner_id: &str, executor_id: &str) -> String {
+        storage_key(KeyPrefix::WithdrawAllowance, &[owner_id, executor_id])
+    }
+
+    fn get_withdraw_allowance_value(&self, key: &str) -> Balance {
+        sdk::read_storage(key.as_bytes())
+            .map(|data| Balance::try_from_slice(&data[..]).unwrap())
+            .unwrap_or(0)
+    }
+
+    fn withdraw_intent_key(&self, id: u64) -> String {
+        storage_key(KeyPrefix::WithdrawIntent, &[&id.to_string()])
+    }
+
+    fn withdraw_intents_count_key(&self) -> String {
+        storage_key(KeyPrefix::WithdrawIntent, &[])
+    }
+
+    /// Ids handed out so far, the next intent gets this id.
+    fn withdraw_intents_count(&self) -> u64 {
+        sdk::read_storage(self.withdraw_intents_count_key().as_bytes())
+            .map(|data| u64::try_from_slice(&data[..]).unwrap())
+            .unwrap_or(0)
+    }
+
+    fn get_withdraw_intent_value(&self, id: u64) -> Option<WithdrawIntent> {
+        sdk::read_storage(self.withdraw_intent_key(id).as_bytes())
+            .map(|data| WithdrawIntent::try_from_slice(&data[..]).unwrap())
+    }
+
+    fn remove_withdraw_intent(&self, id: u64, owner_id: AccountId) {
+        let initial_storage = sdk::storage_usage();
+        sdk::remove_storage(self.withdraw_intent_key(id).as_bytes());
+        let refund =
+            Balance::from(initial_storage - sdk::storage_usage()) * sdk::STORAGE_PRICE_PER_BYTE;
+        if refund > 0 {
+            let promise0 = sdk::promise_batch_create(owner_id);
+            sdk::promise_batch_action_transfer(promise0, refund);
+        }
+    }
+
+    fn alias_key(&self, alias: &str) -> String {
+        storage_key(KeyPrefix::Alias, &[alias])
+    }
+
+    fn block_confirmations_key(&self) -> String {
+        storage_key(KeyPrefix::BlockConfirmations, &[])
+    }
+
+    fn block_confirmations(&self) -> Option<BlockConfirmations> {
+        sdk::read_storage(self.block_confirmations_key().as_bytes())
+            .map(|data| BlockConfirmations::try_from_slice(&data[..]).unwrap())
+    }
+
+    fn webhook_key(&self, id: u64) -> String {
+        storage_key(KeyPrefix::Webhook, &[&id.to_string()])
+    }
+
+    fn webhooks_count_key(&self) -> String {
+        storage_key(KeyPrefix::Webhook, &[])
+    }
+
+    /// Ids handed out so far, the next webhook gets this id.
+    fn webhooks_count(&self) -> u64 {
+        sdk::read_storage(self.webhooks_count_key().as_bytes())
+            .map(|data| u64::try_from_slice(&data[..]).unwrap())
+            .unwrap_or(0)
+    }
+
+    fn get_webhook_descriptor(&self, id: u64) -> Option<WebhookDescriptor> {
+        sdk::read_storage(self.webhook_key(id).as_bytes())
+            .map(|data| WebhookDescriptor::try_from_slice(&data[..]).unwrap())
+    }
+
+    fn webhook_descriptor(owner_id: AccountId, args: RegisterWebhookCallArgs) -> WebhookDescriptor {
+        if let Some(account_id) = &args.account_id {
+            assert!(
+                Self::is_valid_account_id(account_id),
+                "Invalid webhook account filter"
+            );
+        }
+        WebhookDescriptor {
+            owner_id,
+            url_hash: args.url_hash,
+            events: args.events,
+            account_id: args.account_id,
+        }
+    }
+
+    fn get_alias(&self, alias: &str) -> Option<AliasRecord> {
+        sdk::read_storage(self.alias_key(alias).as_bytes())
+            .map(|data| AliasRecord::try_from_slice(&data[..]).unwrap())
+    }
+
+    /// Aliases are short lowercase names, so Ethereum addresses in `0x` form fit as well.
+    pub(crate) fn assert_valid_alias(alias: &str) {
+        Self::check_alias(alias).unwrap_or_else(|err| sdk::panic_utf8(err.as_bytes()));
+    }
+
+    pub(crate) fn check_alias(alias: &str) -> Result<(), &'static str> {
+        if alias.len() < MIN_ALIAS_LEN || alias.len() > MAX_ALIAS_LEN {
+            return Err("Alias length is out of range");
+        }
+        if !alias
+            .bytes()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == b'-' || c == b'_')
+        {
+            return Err("Alias contains invalid characters");
+        }
+        Ok(())
+    }
+
+    /// Syntactic check of a NEAR account id, it doesn't tell whether the account exists.
+    pub(crate) fn is_valid_account_id(account_id: &str) -> bool {
+        let len = account_id.len() as u64;
+        if len < MIN_ACCOUNT_ID_LEN || len > MAX_ACCOUNT_ID_LEN {
+            return false;
+        }
+        // Separators can't lead, trail or follow each other
+        let mut after_separator = true;
+        for c in account_id.bytes() {
+            let is_separator = c == b'-' || c == b'_' || c == b'.';
+            if is_separator && after_separator
+                || !is_separator && !c.is_ascii_lowercase() && !c.is_ascii_digit()
+            {
+                return false;
+            }
+            after_separator = is_separator;
+        }
+        !after_separator
+    }
+
+    /// Resolve `@<alias>` recipients to the account which registered the alias, if it was
+    /// registered before the proof block, so a late registration can't claim the deposit.
+    fn resolve_recipient(&self, recipient: AccountId, proof: &Proof) -> AccountId {
+        self.try_resolve_recipient(recipient, proof)
+            .unwrap_or_else(|failure| match failure {
+                PreflightFailure::AliasRegisteredLate => {
+                    sdk::panic_utf8(ConnectorError::AliasRegisteredLate.as_ref())
+                }
+                _ => sdk::panic_utf8(ConnectorError::UnregisteredAlias.as_ref()),
+            })
+    }
+
+    fn try_resolve_recipient(
+        &self,
+        recipient: AccountId,
+        proof: &Proof,
+    ) -> Result<AccountId, PreflightFailure> {
+        match recipient.strip_prefix(ALIAS_PREFIX) {
+            Some(alias) => {
+                let record = self
+                    .get_alias(alias)
+                    .ok_or(PreflightFailure::UnregisteredAlias)?;
+                let height = proof
+                    .block_number()
+                    .unwrap_or_else(|err| sdk::panic_utf8(err.as_ref()));
+                if height <= record.eth_block_height {
+                    return Err(PreflightFailure::AliasRegisteredLate);
+                }
+                Ok(record.account_id)
+            }
+            None => Ok(recipient),
+        }
+    }
+}
+
+impl AdminControlled for EthConnectorContract {
+    fn get_owner(&self) -> &AccountId {
+        &self.contract.owner_id
+    }
+
+    fn get_paused(&self) -> PausedMask {
+        self.contract.paused_mask
+    }
+
+    fn set_paused(&mut self, paused_mask: PausedMask) {
+        self.contract.paused_mask = paused_mask;
+    }
 }
@@ -1,6 +1,13 @@
 use super::*;
-use ethabi::ParamType;
-use primitive_types::U128;
+use crate::connector::ALIAS_PREFIX;
+use ethabi::{ParamType, Token};
+use primitive_types::{H160, H256, U128};
+
+pub const DEPOSITED_EVENT_NAME: &str = "DepositedToNear";
+/// Upper bound of the recipient field: NEAR account id with room for a routing message.
+pub const MAX_RECIPIENT_LEN: usize = 256;
+/// Separates the recipient account from the message, as in `account.near:message`.
+pub const MESSAGE_SEPARATOR: char = ':';
 
 /// Data that was emitted by the Ethereum Deposited event.
 #[derive(Debug, PartialEq)]
@@ -8,53 +15,138 @@ pub struct EthDepositedEvent {
     pub eth_custodian_address: EthAddress,
     pub sender: AccountId,
     pub recipient: AccountId,
+    /// Message following the recipient account in the recipient field, if any.
+    pub message: Option<String>,
     pub amount: U128,
     pub fee: U128,
 }
 
 impl EthDepositedEvent {
-    fn event_params() -> EthEventParams {
-        vec![
-            ("sender".to_string(), ParamType::Address, true),
-            ("nearRecipient".to_string(), ParamType::String, false),
-            ("amount".to_string(), ParamType::Uint(256), false),
-            ("fee".to_string(), ParamType::Uint(256), false),
-        ]
+    fn event_params(layout: &EventLayout) -> EthEventParams {
+        let params = vec![
+            ("sender".to_string(), ParamType::Address),
+            ("nearRecipient".to_string(), ParamType::String),
+            ("amount".to_string(), ParamType::Uint(256)),
+            ("fee".to_string(), ParamType::Uint(256)),
+        ];
+        params
+            .into_iter()
+            .zip(layout.indexed.iter())
+            .map(|((name, kind), indexed)| (name, kind, *indexed))
+            .collect()
+    }
+
+    /// Topic0 of the compiled-in `DepositedToNear` event signature.
+    pub fn signature_topic() -> RawH256 {
+        let event = EthEvent::event(
+            DEPOSITED_EVENT_NAME,
+            EthDepositedEvent::event_params(&EventLayout::default()),
+        );
+        event.signature().0
     }
 
     /// Parse raw log Etherium proof entry data.
-    pub fn from_log_entry_data(data: &[u8]) -> Self {
+    pub fn from_log_entry_data(data: &[u8], layout: &EventLayout, topic: &RawH256) -> Self {
+        Self::try_from_log_entry_data(data, layout, topic)
+            .unwrap_or_else(|err| sdk::panic_utf8(err.as_ref()))
+    }
+
+    /// Same as `from_log_entry_data`, but a malformed log entry is returned as an error.
+    pub fn try_from_log_entry_data(
+        data: &[u8],
+        layout: &EventLayout,
+        topic: &RawH256,
+    ) -> Result<Self, ConnectorError> {
         let event = EthEvent::fetch_log_entry_data(
-            "DepositedToNear",
-            EthDepositedEvent::event_params(),
+            DEPOSITED_EVENT_NAME,
+            EthDepositedEvent::event_params(layout),
+            topic,
             data,
-        );
+        )?;
         let sender = event.log.params[0].value.clone().into_address().unwrap().0;
         let sender = hex::encode(sender);
 
         let recipient = event.log.params[1].value.clone().to_string();
-        let amount = U128::from(
-            event.log.params[2]
-                .value
-                .clone()
-                .into_uint()
-                .unwrap()
-                .as_u128(),
-        );
-        let fee = U128::from(
-            event.log.params[3]
-                .value
-                .clone()
-                .into_uint()
-                .unwrap()
-                .as_u128(),
-        );
-        Self {
+        if recipient.is_empty()
+            || recipient.len() > MAX_RECIPIENT_LEN
+            || recipient.chars().any(|c| c.is_control())
+        {
+            return Err(ConnectorError::InvalidRecipient);
+        }
+        let (recipient, message) = match recipient.find(MESSAGE_SEPARATOR) {
+            Some(pos) => (
+                recipient[..pos].to_string(),
+                Some(recipient[pos + 1..].to_string()),
+            ),
+            None => (recipient, None),
+        };
+        // Tokens minted to a malformed account id could never be moved
+        let is_valid_recipient = match recipient.strip_prefix(ALIAS_PREFIX) {
+            Some(alias) => EthConnectorContract::check_alias(alias).is_ok(),
+            None => EthConnectorContract::is_valid_account_id(&recipient),
+        };
+        if !is_valid_recipient {
+            return Err(ConnectorError::InvalidRecipient);
+        }
+        let amount = Self::wei_to_balance(event.log.params[2].value.clone())?;
+        let fee = Self::wei_to_balance(event.log.params[3].value.clone())?;
+        Ok(Self {
             eth_custodian_address: event.eth_custodian_address,
             sender,
             recipient,
+            message,
             amount,
             fee,
+        })
+    }
+
+    /// Wei amounts are `uint256` on Ethereum, reject those which don't fit a NEAR `Balance`.
+    fn wei_to_balance(token: Token) -> Result<U128, ConnectorError> {
+        let value = token.into_uint().unwrap();
+        if value.bits() > 128 {
+            return Err(ConnectorError::AmountOverflow);
+        }
+        Ok(U128::from(value.low_u128()))
+    }
+
+    /// Encode the event into RLP `log_entry_data` identical to the one emitted
+    /// by the custodian contract. Inverse of `from_log_entry_data`.
+    pub fn to_log_entry_data(&self, layout: &EventLayout) -> Vec<u8> {
+        let event = EthEvent::event(
+            DEPOSITED_EVENT_NAME,
+            EthDepositedEvent::event_params(layout),
+        );
+        let sender = hex::decode(&self.sender).expect("Sender should be a valid hex string.");
+        let tokens = vec![
+            Token::Address(ethabi::Address::from_slice(&sender)),
+            Token::String(self.recipient_field()),
+            Token::Uint(self.amount.as_u128().into()),
+            Token::Uint(self.fee.as_u128().into()),
+        ];
+
+        let mut topics = vec![H256(event.signature().0)];
+        let mut data_tokens = Vec::new();
+        for (token, indexed) in tokens.into_iter().zip(layout.indexed.iter()) {
+            if *indexed {
+                // Indexed params of static types are stored as their 32-byte ABI encoding
+                topics.push(H256::from_slice(&ethabi::encode(&[token])));
+            } else {
+                data_tokens.push(token);
+            }
+        }
+        let log_entry = LogEntry {
+            address: H160(self.eth_custodian_address.0),
+            topics,
+            data: ethabi::encode(&data_tokens),
+        };
+        rlp::encode(&log_entry).to_vec()
+    }
+
+    /// Recipient field as emitted by the custodian, with the message joined back.
+    fn recipient_field(&self) -> String {
+        match &self.message {
+            Some(message) => format!("{}{}{}", self.recipient, MESSAGE_SEPARATOR, message),
+            None => self.recipient.clone(),
         }
     }
 }
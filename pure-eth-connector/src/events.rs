@@ -0,0 +1,239 @@
+use super::*;
+
+const EVENT_JSON_PREFIX: &str = "EVENT_JSON:";
+const EVENT_STANDARD: &str = "eth-connector";
+const EVENT_VERSION: &str = "1.0.0";
+
+/// Quote `value` as a JSON string. Recipient and account ids can come from an Ethereum
+/// event, so they can't be put in the log as is.
+fn json_string(value: &str) -> String {
+    let mut res = String::with_capacity(value.len() + 2);
+    res.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => res.push_str("\\\""),
+            '\\' => res.push_str("\\\\"),
+            c if (c as u32) < 0x20 => res.push_str(&format!("\\u{:04x}", c as u32)),
+            c => res.push(c),
+        }
+    }
+    res.push('"');
+    res
+}
+
+/// Emit a NEP-297 event log: `EVENT_JSON:{"standard":..,"version":..,"event":..,"data":[..]}`.
+/// `data` must already be a serialized JSON object.
+/// Payload types for consumers are published in the `eth-connector-events` crate,
+/// keep both in sync.
+fn emit(event: &str, data: String) {
+    sdk::log(format!(
+        r#"{}{{"standard":"{}","version":"{}","event":"{}","data":[{}]}}"#,
+        EVENT_JSON_PREFIX, EVENT_STANDARD, EVENT_VERSION, event, data
+    ));
+}
+
+/// Deposit proof submitted, `trace_id` follows it through the rest of the flow.
+pub fn emit_deposit_submitted(trace_id: &str, proof_key: &str, relayer_id: &AccountId) {
+    emit(
+        "deposit_submitted",
+        format!(
+            r#"{{"trace_id":{},"proof_key":{},"relayer_id":{}}}"#,
+            json_string(trace_id),
+            json_string(proof_key),
+            json_string(relayer_id)
+        ),
+    );
+}
+
+/// Deposit finalized, attributed to the account which submitted its proof.
+/// `config_hash` identifies the configuration it was processed under.
+pub fn emit_deposit(
+    proof_key: &str,
+    new_owner_id: &AccountId,
+    amount: Balance,
+    fee: Balance,
+    protocol_fee: Balance,
+    relayer_id: &AccountId,
+    trace_id: &str,
+    config_hash: &str,
+) {
+    emit(
+        "deposit",
+        format!(
+            r#"{{"proof_key":{},"new_owner_id":{},"amount":"{}","fee":"{}","protocol_fee":"{}","relayer_id":{},"trace_id":{},"config_hash":{}}}"#,
+            json_string(proof_key),
+            json_string(new_owner_id),
+            amount,
+            fee,
+            protocol_fee,
+            json_string(relayer_id),
+            json_string(trace_id),
+            json_string(config_hash)
+        ),
+    );
+}
+
+/// Shadow prover result differing from the primary prover one. `shadow_verified`
+/// is `null` when the shadow prover call failed.
+pub fn emit_prover_disagreement(
+    proof_key: &str,
+    shadow_prover_id: &AccountId,
+    verified: bool,
+    shadow_verified: Option<bool>,
+) {
+    let shadow_verified = match shadow_verified {
+        Some(shadow_verified) => shadow_verified.to_string(),
+        None => "null".into(),
+    };
+    emit(
+        "prover_disagreement",
+        format!(
+            r#"{{"proof_key":{},"shadow_prover_id":{},"verified":{},"shadow_verified":{}}}"#,
+            json_string(proof_key),
+            json_string(shadow_prover_id),
+            verified,
+            shadow_verified
+        ),
+    );
+}
+
+/// Engine deposit undone after the engine failed to credit the recipient,
+/// its proof can be submitted again.
+pub fn emit_deposit_rolled_back(proof_key: &str, trace_id: &str, engine_account_id: &AccountId) {
+    emit(
+        "deposit_rolled_back",
+        format!(
+            r#"{{"proof_key":{},"trace_id":{},"engine_account_id":{}}}"#,
+            json_string(proof_key),
+            json_string(trace_id),
+            json_string(engine_account_id)
+        ),
+    );
+}
+
+/// Deposit message not handled by the recipient, which keeps the tokens as for
+/// a deposit without a message.
+pub fn emit_deposit_transfer_fallback(trace_id: &str, receiver_id: &AccountId, amount: Balance) {
+    emit(
+        "deposit_transfer_fallback",
+        format!(
+            r#"{{"trace_id":{},"receiver_id":{},"amount":"{}"}}"#,
+            json_string(trace_id),
+            json_string(receiver_id),
+            amount
+        ),
+    );
+}
+
+/// Tokens burned for a withdrawal to Ethereum.
+pub fn emit_withdraw(
+    owner_id: &AccountId,
+    eth_recipient: &EthAddress,
+    amount: Balance,
+    fee: Balance,
+    config_hash: &str,
+) {
+    emit(
+        "withdraw",
+        format!(
+            r#"{{"owner_id":{},"eth_recipient":"{}","amount":"{}","fee":"{}","config_hash":{}}}"#,
+            json_string(owner_id),
+            hex::encode(eth_recipient.0),
+            amount,
+            fee,
+            json_string(config_hash)
+        ),
+    );
+}
+
+/// Deposit fee distributed between the treasury and the relayer which submitted the proof.
+pub fn emit_deposit_fee_split(
+    treasury_id: &str,
+    treasury_fee: Balance,
+    relayer_id: &AccountId,
+    relayer_fee: Balance,
+) {
+    emit(
+        "deposit_fee_split",
+        format!(
+            r#"{{"treasury_id":{},"treasury_fee":"{}","relayer_id":{},"relayer_fee":"{}"}}"#,
+            json_string(treasury_id),
+            treasury_fee,
+            json_string(relayer_id),
+            relayer_fee
+        ),
+    );
+}
+
+/// Deposit left retryable because its prover call didn't succeed.
+pub fn emit_deposit_stuck(proof_key: &str, relayer_id: &AccountId, trace_id: &str) {
+    emit(
+        "deposit_stuck",
+        format!(
+            r#"{{"proof_key":{},"relayer_id":{},"trace_id":{}}}"#,
+            json_string(proof_key),
+            json_string(relayer_id),
+            json_string(trace_id)
+        ),
+    );
+}
+
+/// Verified deposit left for `complete_deposit` as too little gas was left to mint it.
+pub fn emit_deposit_deferred(proof_key: &str, trace_id: &str) {
+    emit(
+        "deposit_deferred",
+        format!(
+            r#"{{"proof_key":{},"trace_id":{}}}"#,
+            json_string(proof_key),
+            json_string(trace_id)
+        ),
+    );
+}
+
+/// Accepted proof revoked after a reorg, with the amount burned from `clawback_account_id`.
+pub fn emit_proof_revoked(
+    proof_key: &str,
+    clawback_account_id: &Option<AccountId>,
+    clawed_back: Balance,
+) {
+    let clawback_account_id = match clawback_account_id {
+        Some(account_id) => json_string(account_id),
+        None => "null".into(),
+    };
+    emit(
+        "proof_revoked",
+        format!(
+            r#"{{"proof_key":{},"clawback_account_id":{},"clawed_back":"{}"}}"#,
+            json_string(proof_key),
+            clawback_account_id,
+            clawed_back
+        ),
+    );
+}
+
+/// Withdrawals to an Ethereum address were blocked or unblocked by the admin.
+pub fn emit_eth_recipient_block(eth_address: &EthAddress, blocked: bool) {
+    emit(
+        "eth_recipient_block",
+        format!(
+            r#"{{"eth_address":"{}","blocked":{}}}"#,
+            hex::encode(eth_address.0),
+            blocked
+        ),
+    );
+}
+
+/// Minted-minus-burned totals don't match the token total supply.
+pub fn emit_supply_discrepancy(
+    total_minted: Balance,
+    total_burned: Balance,
+    total_supply: Balance,
+) {
+    emit(
+        "supply_discrepancy",
+        format!(
+            r#"{{"total_minted":"{}","total_burned":"{}","total_supply":"{}"}}"#,
+            total_minted, total_burned, total_supply
+        ),
+    );
+}
@@ -0,0 +1,4 @@
+/// Upper bound of the protocol deposit fee: 10%.
+pub const MAX_DEPOSIT_FEE_BPS: u16 = 1_000;
+/// Upper bound of the withdrawal fee: 10%.
+pub const MAX_WITHDRAW_FEE_BPS: u16 = 1_000;
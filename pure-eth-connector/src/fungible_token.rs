@@ -1,9 +1,15 @@
 #![allow(dead_code)]
 use super::*;
-use crate::connector::CONTRACT_FT_KEY;
+use crate::connector::{storage_key, KeyPrefix, CONTRACT_FT_KEY};
 
-const GAS_FOR_RESOLVE_TRANSFER: Gas = 5_000_000_000_000;
-const GAS_FOR_FT_TRANSFER_CALL: Gas = 25_000_000_000_000 + GAS_FOR_RESOLVE_TRANSFER;
+pub const GAS_FOR_RESOLVE_TRANSFER: Gas = 5_000_000_000_000;
+pub const GAS_FOR_FT_TRANSFER_CALL: Gas = 25_000_000_000_000 + GAS_FOR_RESOLVE_TRANSFER;
+
+/// NEP-148 metadata of the bridged nETH token.
+pub const FT_METADATA_SPEC: &str = "ft-1.0.0";
+pub const FT_NAME: &str = "Ether";
+pub const FT_SYMBOL: &str = "nETH";
+pub const FT_DECIMALS: u8 = 18;
 
 #[derive(Debug, BorshDeserialize, BorshSerialize)]
 pub struct FungibleToken {
@@ -12,6 +18,16 @@ pub struct FungibleToken {
 
     /// The storage size in bytes for one account.
     pub account_storage_usage: StorageUsage,
+
+    /// Number of registered accounts.
+    pub accounts_counter: u64,
+}
+
+/// `FungibleToken` layout of state version 1.
+#[derive(BorshDeserialize)]
+pub struct FungibleTokenV1 {
+    pub total_supply: Balance,
+    pub account_storage_usage: StorageUsage,
 }
 
 impl Default for fungible_token::FungibleToken {
@@ -25,6 +41,7 @@ impl FungibleToken {
         Self {
             total_supply: 0,
             account_storage_usage: 0,
+            accounts_counter: 0,
         }
     }
 
@@ -100,6 +117,14 @@ impl FungibleToken {
         self.total_supply
     }
 
+    /// NEP-148 token metadata, serialized as JSON for wallets.
+    pub fn ft_metadata(&self) -> String {
+        format!(
+            r#"{{"spec":"{}","name":"{}","symbol":"{}","icon":null,"reference":null,"reference_hash":null,"decimals":{}}}"#,
+            FT_METADATA_SPEC, FT_NAME, FT_SYMBOL, FT_DECIMALS
+        )
+    }
+
     pub fn ft_balance_of(&self, account_id: AccountId) -> u128 {
         if let Some(data) = self.accounts_get(account_id) {
             u128::try_from_slice(&data[..]).unwrap()
@@ -340,7 +365,10 @@ impl FungibleToken {
         [CONTRACT_FT_KEY, &account_id].join(".")
     }
 
-    pub fn accounts_insert(&self, account_id: AccountId, amount: Balance) {
+    pub fn accounts_insert(&mut self, account_id: AccountId, amount: Balance) {
+        if !self.accounts_contains_key(account_id.clone()) {
+            self.holders_push(account_id.clone());
+        }
         sdk::save_contract(self.ft_key(account_id).as_str(), &amount)
     }
 
@@ -348,10 +376,69 @@ impl FungibleToken {
         sdk::storage_has_key(self.ft_key(account_id).as_str())
     }
 
-    fn accounts_remove(&self, account_id: AccountId) {
+    fn accounts_remove(&mut self, account_id: AccountId) {
+        if self.accounts_contains_key(account_id.clone()) {
+            self.holders_remove(account_id.clone());
+        }
         sdk::remove_storage(self.ft_key(account_id).as_bytes())
     }
 
+    /// Registered accounts with their balances, paginated by registration index.
+    pub fn ft_holders(&self, from_index: u64, limit: u64) -> Vec<(AccountId, Balance)> {
+        let to_index = from_index.saturating_add(limit).min(self.accounts_counter);
+        (from_index..to_index)
+            .map(|index| {
+                let account_id: AccountId = sdk::get_contract_data(&self.holder_key(index));
+                let balance = self.ft_balance_of(account_id.clone());
+                (account_id, balance)
+            })
+            .collect()
+    }
+
+    fn holder_key(&self, index: u64) -> String {
+        storage_key(KeyPrefix::Holder, &[&index.to_string()])
+    }
+
+    fn holder_index_key(&self, account_id: &str) -> String {
+        storage_key(KeyPrefix::HolderIndex, &[account_id])
+    }
+
+    fn holders_push(&mut self, account_id: AccountId) {
+        let index = self.accounts_counter;
+        sdk::save_contract(&self.holder_key(index), &account_id);
+        sdk::save_contract(&self.holder_index_key(&account_id), &index);
+        self.accounts_counter += 1;
+    }
+
+    /// Add a registered account missing from the holders index.
+    pub fn backfill_holder(&mut self, account_id: AccountId) {
+        assert!(
+            self.accounts_contains_key(account_id.clone()),
+            "The account is not registered"
+        );
+        if !sdk::storage_has_key(&self.holder_index_key(&account_id)) {
+            self.holders_push(account_id);
+        }
+    }
+
+    /// Swap-remove the account from the holders index to keep it dense.
+    fn holders_remove(&mut self, account_id: AccountId) {
+        // Accounts registered before the index may not have been backfilled yet
+        let index: u64 = match sdk::read_storage(self.holder_index_key(&account_id).as_bytes()) {
+            Some(data) => u64::try_from_slice(&data[..]).unwrap(),
+            None => return,
+        };
+        let last_index = self.accounts_counter - 1;
+        if index != last_index {
+            let last_account: AccountId = sdk::get_contract_data(&self.holder_key(last_index));
+            sdk::save_contract(&self.holder_key(index), &last_account);
+            sdk::save_contract(&self.holder_index_key(&last_account), &index);
+        }
+        sdk::remove_storage(self.holder_key(last_index).as_bytes());
+        sdk::remove_storage(self.holder_index_key(&account_id).as_bytes());
+        self.accounts_counter = last_index;
+    }
+
     pub fn accounts_get(&self, account_id: AccountId) -> Option<Vec<u8>> {
         sdk::read_storage(self.ft_key(account_id).as_bytes())
     }
@@ -1,6 +1,6 @@
 use super::*;
 
-use core::convert::From;
+use core::convert::{From, TryFrom};
 use rjson::{Array, Null, Object, Value};
 
 pub enum JsonValue {
@@ -36,6 +36,18 @@ impl JsonValue {
         }
     }
 
+    /// Same as `u64`, panicking when the number doesn't fit instead of truncating it.
+    pub fn u16(&self, key: &str) -> Result<u16, ()> {
+        self.u64(key)
+            .map(|n| u16::try_from(n).unwrap_or_else(|_| sdk::panic_utf8(FAILED_PARSE.as_bytes())))
+    }
+
+    /// Same as `u64`, panicking when the number doesn't fit instead of truncating it.
+    pub fn u8(&self, key: &str) -> Result<u8, ()> {
+        self.u64(key)
+            .map(|n| u8::try_from(n).unwrap_or_else(|_| sdk::panic_utf8(FAILED_PARSE.as_bytes())))
+    }
+
     pub fn u128(&self, key: &str) -> Result<u128, ()> {
         match self {
             json::JsonValue::Object(o) => match o.get(key).ok_or(())? {
@@ -58,7 +70,21 @@ impl JsonValue {
 
     pub fn parse_u8(v: &JsonValue) -> u8 {
         match v {
-            json::JsonValue::Number(n) => *n as u8,
+            json::JsonValue::Number(n) if *n >= 0.0 && *n <= u8::MAX as f64 => *n as u8,
+            _ => sdk::panic_utf8(FAILED_PARSE.as_bytes()),
+        }
+    }
+
+    pub fn parse_bool(v: &JsonValue) -> bool {
+        match v {
+            json::JsonValue::Bool(b) => *b,
+            _ => sdk::panic_utf8(FAILED_PARSE.as_bytes()),
+        }
+    }
+
+    pub fn parse_string(v: &JsonValue) -> String {
+        match v {
+            json::JsonValue::String(s) => s.clone(),
             _ => sdk::panic_utf8(FAILED_PARSE.as_bytes()),
         }
     }
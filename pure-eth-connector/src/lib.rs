@@ -6,20 +6,31 @@
 #![feature(panic_info_message)]
 extern crate alloc;
 
+mod admin_controlled;
+mod bps;
 mod connector;
 mod deposit_event;
+mod events;
+mod fee;
 mod fungible_token;
 mod json;
 mod log_entry;
+mod promise;
 mod prover;
 mod sdk;
+#[cfg(feature = "testvectors")]
+mod test_vectors;
 mod types;
 
+pub use crate::admin_controlled::*;
+pub use crate::bps::*;
 pub use crate::connector::EthConnectorContract;
 pub use crate::deposit_event::*;
+pub use crate::fee::*;
 pub use crate::fungible_token::*;
 pub use crate::json::parse_json;
 pub use crate::log_entry::*;
+pub use crate::promise::*;
 pub use crate::prover::*;
 pub use crate::types::*;
 pub use alloc::{
@@ -77,16 +88,491 @@ pub extern "C" fn withdraw() {
     EthConnectorContract::new().withdraw()
 }
 
+#[no_mangle]
+pub extern "C" fn withdraw_for() {
+    EthConnectorContract::new().withdraw_for()
+}
+
+#[no_mangle]
+pub extern "C" fn propose_owner() {
+    EthConnectorContract::new().propose_owner()
+}
+
+#[no_mangle]
+pub extern "C" fn accept_owner() {
+    EthConnectorContract::new().accept_owner()
+}
+
+#[no_mangle]
+pub extern "C" fn get_owner_id() {
+    EthConnectorContract::new().get_owner_id()
+}
+
+#[no_mangle]
+pub extern "C" fn set_eth_recipient_blocked() {
+    EthConnectorContract::new().set_eth_recipient_blocked()
+}
+
+#[no_mangle]
+pub extern "C" fn is_eth_recipient_blocked() {
+    EthConnectorContract::new().is_eth_recipient_blocked()
+}
+
+#[no_mangle]
+pub extern "C" fn approve_withdraw_executor() {
+    EthConnectorContract::new().approve_withdraw_executor()
+}
+
+#[no_mangle]
+pub extern "C" fn revoke_withdraw_executor() {
+    EthConnectorContract::new().revoke_withdraw_executor()
+}
+
+#[no_mangle]
+pub extern "C" fn get_withdraw_allowance() {
+    EthConnectorContract::new().get_withdraw_allowance()
+}
+
+#[no_mangle]
+pub extern "C" fn authorize_withdraw_intent() {
+    EthConnectorContract::new().authorize_withdraw_intent()
+}
+
+#[no_mangle]
+pub extern "C" fn execute_withdraw_intent() {
+    EthConnectorContract::new().execute_withdraw_intent()
+}
+
+#[no_mangle]
+pub extern "C" fn revoke_withdraw_intent() {
+    EthConnectorContract::new().revoke_withdraw_intent()
+}
+
+#[no_mangle]
+pub extern "C" fn get_withdraw_intent() {
+    EthConnectorContract::new().get_withdraw_intent()
+}
+
+#[no_mangle]
+pub extern "C" fn deposit_if_unused() {
+    EthConnectorContract::new().deposit_if_unused()
+}
+
 #[no_mangle]
 pub extern "C" fn finish_deposit() {
     EthConnectorContract::new().finish_deposit();
 }
 
+#[no_mangle]
+pub extern "C" fn complete_deposit() {
+    EthConnectorContract::new().complete_deposit();
+}
+
+#[no_mangle]
+pub extern "C" fn finish_engine_deposit() {
+    EthConnectorContract::new().finish_engine_deposit();
+}
+
+#[no_mangle]
+pub extern "C" fn finish_deposit_transfer() {
+    EthConnectorContract::new().finish_deposit_transfer();
+}
+
+#[no_mangle]
+pub extern "C" fn register_alias() {
+    EthConnectorContract::new().register_alias()
+}
+
+#[no_mangle]
+pub extern "C" fn finish_register_alias() {
+    EthConnectorContract::new().finish_register_alias()
+}
+
+#[no_mangle]
+pub extern "C" fn get_alias_account() {
+    EthConnectorContract::new().get_alias_account()
+}
+
+#[no_mangle]
+pub extern "C" fn register_webhook() {
+    EthConnectorContract::new().register_webhook()
+}
+
+#[no_mangle]
+pub extern "C" fn update_webhook() {
+    EthConnectorContract::new().update_webhook()
+}
+
+#[no_mangle]
+pub extern "C" fn remove_webhook() {
+    EthConnectorContract::new().remove_webhook()
+}
+
+#[no_mangle]
+pub extern "C" fn get_webhook() {
+    EthConnectorContract::new().get_webhook()
+}
+
+#[no_mangle]
+pub extern "C" fn get_webhooks() {
+    EthConnectorContract::new().get_webhooks()
+}
+
+#[no_mangle]
+pub extern "C" fn set_deposited_event_layout() {
+    EthConnectorContract::new().set_deposited_event_layout()
+}
+
+#[no_mangle]
+pub extern "C" fn get_deposited_event_layout() {
+    EthConnectorContract::new().get_deposited_event_layout()
+}
+
+#[no_mangle]
+pub extern "C" fn set_engine_account() {
+    EthConnectorContract::new().set_engine_account()
+}
+
+#[no_mangle]
+pub extern "C" fn set_relayer_fee_share() {
+    EthConnectorContract::new().set_relayer_fee_share()
+}
+
+#[no_mangle]
+pub extern "C" fn set_deposit_fee() {
+    EthConnectorContract::new().set_deposit_fee()
+}
+
+#[no_mangle]
+pub extern "C" fn set_withdraw_fee() {
+    EthConnectorContract::new().set_withdraw_fee()
+}
+
+#[no_mangle]
+pub extern "C" fn claim_fees() {
+    EthConnectorContract::new().claim_fees()
+}
+
+#[no_mangle]
+pub extern "C" fn get_fee_config() {
+    EthConnectorContract::new().get_fee_config()
+}
+
+#[no_mangle]
+pub extern "C" fn get_effective_limits() {
+    EthConnectorContract::new().get_effective_limits()
+}
+
+#[no_mangle]
+pub extern "C" fn schedule_relayer_fee_share() {
+    EthConnectorContract::new().schedule_relayer_fee_share()
+}
+
+#[no_mangle]
+pub extern "C" fn irreversibly_freeze() {
+    EthConnectorContract::new().irreversibly_freeze()
+}
+
+#[no_mangle]
+pub extern "C" fn get_frozen_flags() {
+    EthConnectorContract::new().get_frozen_flags()
+}
+
+#[no_mangle]
+pub extern "C" fn set_refusal_policy() {
+    EthConnectorContract::new().set_refusal_policy()
+}
+
+#[no_mangle]
+pub extern "C" fn get_refusal_policy() {
+    EthConnectorContract::new().get_refusal_policy()
+}
+
+#[no_mangle]
+pub extern "C" fn set_shadow_prover() {
+    EthConnectorContract::new().set_shadow_prover()
+}
+
+#[no_mangle]
+pub extern "C" fn get_shadow_prover() {
+    EthConnectorContract::new().get_shadow_prover()
+}
+
+#[no_mangle]
+pub extern "C" fn set_block_confirmations() {
+    EthConnectorContract::new().set_block_confirmations()
+}
+
+#[no_mangle]
+pub extern "C" fn get_block_confirmations() {
+    EthConnectorContract::new().get_block_confirmations()
+}
+
+#[no_mangle]
+pub extern "C" fn cancel_scheduled_changes() {
+    EthConnectorContract::new().cancel_scheduled_changes()
+}
+
+#[no_mangle]
+pub extern "C" fn schedule_deposited_event_topic() {
+    EthConnectorContract::new().schedule_deposited_event_topic()
+}
+
+#[no_mangle]
+pub extern "C" fn get_event_topics() {
+    EthConnectorContract::new().get_event_topics()
+}
+
+#[no_mangle]
+pub extern "C" fn set_timelock_delay() {
+    EthConnectorContract::new().set_timelock_delay()
+}
+
+#[no_mangle]
+pub extern "C" fn stage_action() {
+    EthConnectorContract::new().stage_action()
+}
+
+#[no_mangle]
+pub extern "C" fn execute_action() {
+    EthConnectorContract::new().execute_action()
+}
+
+#[no_mangle]
+pub extern "C" fn cancel_action() {
+    EthConnectorContract::new().cancel_action()
+}
+
+#[no_mangle]
+pub extern "C" fn get_timelock() {
+    EthConnectorContract::new().get_timelock()
+}
+
+#[no_mangle]
+pub extern "C" fn get_scheduled_changes() {
+    EthConnectorContract::new().get_scheduled_changes()
+}
+
+#[no_mangle]
+pub extern "C" fn set_maintenance_mode() {
+    EthConnectorContract::new().set_maintenance_mode()
+}
+
+#[no_mangle]
+pub extern "C" fn get_status() {
+    EthConnectorContract::new().get_status()
+}
+
+#[no_mangle]
+pub extern "C" fn get_account_history() {
+    EthConnectorContract::new().get_account_history()
+}
+
+#[no_mangle]
+pub extern "C" fn get_gas_budget() {
+    EthConnectorContract::new().get_gas_budget()
+}
+
+#[no_mangle]
+pub extern "C" fn get_deposit_storage_cost() {
+    EthConnectorContract::new().get_deposit_storage_cost()
+}
+
+#[no_mangle]
+pub extern "C" fn get_operator_tasks() {
+    EthConnectorContract::new().get_operator_tasks()
+}
+
+#[no_mangle]
+pub extern "C" fn get_stuck_submissions() {
+    EthConnectorContract::new().get_stuck_submissions()
+}
+
+#[no_mangle]
+pub extern "C" fn clear_stuck_submission() {
+    EthConnectorContract::new().clear_stuck_submission()
+}
+
+#[no_mangle]
+pub extern "C" fn get_last_eth_block_height() {
+    EthConnectorContract::new().get_last_eth_block_height()
+}
+
+#[no_mangle]
+pub extern "C" fn set_registry() {
+    EthConnectorContract::new().set_registry()
+}
+
+#[no_mangle]
+pub extern "C" fn register_self() {
+    EthConnectorContract::new().register_self()
+}
+
+#[no_mangle]
+pub extern "C" fn get_registry() {
+    EthConnectorContract::new().get_registry()
+}
+
+#[no_mangle]
+pub extern "C" fn set_eth_custodian_address() {
+    EthConnectorContract::new().set_eth_custodian_address()
+}
+
+#[no_mangle]
+pub extern "C" fn set_prover_account() {
+    EthConnectorContract::new().set_prover_account()
+}
+
+#[no_mangle]
+pub extern "C" fn get_custodian_history() {
+    EthConnectorContract::new().get_custodian_history()
+}
+
+#[no_mangle]
+pub extern "C" fn get_custodian_info() {
+    EthConnectorContract::new().get_custodian_info()
+}
+
+#[no_mangle]
+pub extern "C" fn get_paused_flags() {
+    EthConnectorContract::new().get_paused_flags()
+}
+
+#[no_mangle]
+pub extern "C" fn set_paused_flags() {
+    EthConnectorContract::new().set_paused_flags()
+}
+
+#[no_mangle]
+pub extern "C" fn get_accounts_counter() {
+    EthConnectorContract::new().get_accounts_counter()
+}
+
+#[no_mangle]
+pub extern "C" fn backfill_holders() {
+    EthConnectorContract::new().backfill_holders()
+}
+
+#[no_mangle]
+pub extern "C" fn get_accounts() {
+    EthConnectorContract::new().get_accounts()
+}
+
+#[no_mangle]
+pub extern "C" fn set_code_hash_expectation() {
+    EthConnectorContract::new().set_code_hash_expectation()
+}
+
+#[no_mangle]
+pub extern "C" fn get_code_hash_expectation() {
+    EthConnectorContract::new().get_code_hash_expectation()
+}
+
+#[no_mangle]
+pub extern "C" fn upgrade() {
+    EthConnectorContract::new().upgrade()
+}
+
+#[no_mangle]
+pub extern "C" fn migrate_state() {
+    EthConnectorContract::migrate_state()
+}
+
+#[no_mangle]
+pub extern "C" fn get_schema_log() {
+    EthConnectorContract::new().get_schema_log()
+}
+
+#[no_mangle]
+pub extern "C" fn migrate() {
+    EthConnectorContract::new().migrate()
+}
+
+#[no_mangle]
+pub extern "C" fn preflight() {
+    EthConnectorContract::new().preflight()
+}
+
+#[no_mangle]
+pub extern "C" fn find_deposit() {
+    EthConnectorContract::new().find_deposit()
+}
+
+#[no_mangle]
+pub extern "C" fn get_proof_key() {
+    EthConnectorContract::new().get_proof_key()
+}
+
+#[no_mangle]
+pub extern "C" fn is_used_proof() {
+    EthConnectorContract::new().is_used_proof()
+}
+
+#[no_mangle]
+pub extern "C" fn is_used_event() {
+    EthConnectorContract::new().is_used_event()
+}
+
+#[no_mangle]
+pub extern "C" fn get_proof_submitter() {
+    EthConnectorContract::new().get_proof_submitter()
+}
+
+#[no_mangle]
+pub extern "C" fn prune_used_events() {
+    EthConnectorContract::new().prune_used_events()
+}
+
+#[no_mangle]
+pub extern "C" fn set_prune_horizon() {
+    EthConnectorContract::new().set_prune_horizon()
+}
+
+#[no_mangle]
+pub extern "C" fn get_used_event_pruning() {
+    EthConnectorContract::new().get_used_event_pruning()
+}
+
+#[no_mangle]
+pub extern "C" fn get_proof_prover() {
+    EthConnectorContract::new().get_proof_prover()
+}
+
+#[no_mangle]
+pub extern "C" fn revoke_proof() {
+    EthConnectorContract::new().revoke_proof()
+}
+
+#[no_mangle]
+pub extern "C" fn get_revoked_proof() {
+    EthConnectorContract::new().get_revoked_proof()
+}
+
+#[no_mangle]
+pub extern "C" fn filter_unused_proof_keys() {
+    EthConnectorContract::new().filter_unused_proof_keys()
+}
+
+#[no_mangle]
+pub extern "C" fn check_migration_correctness() {
+    EthConnectorContract::new().check_migration_correctness()
+}
+
+#[no_mangle]
+pub extern "C" fn reconcile() {
+    EthConnectorContract::new().reconcile()
+}
+
 #[no_mangle]
 pub extern "C" fn ft_total_supply() {
     EthConnectorContract::new().ft_total_supply();
 }
 
+#[no_mangle]
+pub extern "C" fn ft_metadata() {
+    EthConnectorContract::new().ft_metadata();
+}
+
 #[no_mangle]
 pub extern "C" fn ft_balance_of() {
     EthConnectorContract::new().ft_balance_of();
@@ -122,7 +608,16 @@ pub extern "C" fn storage_balance_of() {
     EthConnectorContract::new().storage_balance_of()
 }
 
-/// TODO: will be removed - for eth-prover tests only
+/// Canonical encoding vectors, see `test_vectors`.
+#[cfg(feature = "testvectors")]
+#[no_mangle]
+pub extern "C" fn get_test_vectors() {
+    let res = test_vectors::test_vectors().try_to_vec().unwrap();
+    sdk::value_return(&res[..]);
+}
+
+/// Prover stub for the simulation tests, where the connector is its own prover.
+#[cfg(feature = "integration-test")]
 #[no_mangle]
 pub extern "C" fn verify_log_entry() {
     #[cfg(feature = "log")]
@@ -131,7 +626,23 @@ pub extern "C" fn verify_log_entry() {
     sdk::value_return(&data[..]);
 }
 
-/// TODO: will be removed - for eth-prover tests only
+/// Deposit finished synchronously with a mocked prover result, for the simulation tests.
+#[cfg(feature = "integration-test")]
+#[no_mangle]
+pub extern "C" fn mock_deposit() {
+    EthConnectorContract::new().mock_deposit()
+}
+
+/// Light client stub for the simulation tests, ten blocks past the test proof.
+#[cfg(feature = "integration-test")]
+#[no_mangle]
+pub extern "C" fn last_block_number() {
+    let data = 9_782_748u64.try_to_vec().unwrap();
+    sdk::value_return(&data[..]);
+}
+
+/// Receiver stub for the simulation tests.
+#[cfg(feature = "integration-test")]
 #[no_mangle]
 pub extern "C" fn ft_on_transfer() {
     #[cfg(feature = "log")]
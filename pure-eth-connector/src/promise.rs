@@ -0,0 +1,154 @@
+use super::*;
+
+pub type PromiseId = u64;
+
+/// Cross-contract function call.
+#[derive(Clone)]
+pub struct PromiseCreateArgs {
+    pub target_account_id: AccountId,
+    pub method: &'static [u8],
+    pub args: Vec<u8>,
+    pub attached_balance: Balance,
+    pub attached_gas: Gas,
+}
+
+/// Orchestration of cross-contract calls and their results, so the deposit flow
+/// can be driven by a mocked dispatcher instead of the NEAR host.
+pub trait PromiseHandler {
+    fn promise_results_count(&self) -> u64;
+    fn promise_result(&self, index: u64) -> PromiseResult;
+    fn promise_create_call(&mut self, args: &PromiseCreateArgs) -> PromiseId;
+    fn promise_attach_callback(
+        &mut self,
+        base: PromiseId,
+        callback: &PromiseCreateArgs,
+    ) -> PromiseId;
+    fn promise_and(&mut self, promises: &[PromiseId]) -> PromiseId;
+    fn promise_transfer(&mut self, account_id: AccountId, amount: Balance) -> PromiseId;
+    fn promise_return(&mut self, promise: PromiseId);
+    fn value_return(&mut self, value: &[u8]);
+}
+
+/// Dispatcher backed by the NEAR host functions.
+pub struct Runtime;
+
+impl PromiseHandler for Runtime {
+    fn promise_results_count(&self) -> u64 {
+        sdk::promise_results_count()
+    }
+
+    fn promise_result(&self, index: u64) -> PromiseResult {
+        sdk::promise_result(index)
+    }
+
+    fn promise_create_call(&mut self, args: &PromiseCreateArgs) -> PromiseId {
+        sdk::promise_create(
+            args.target_account_id.clone(),
+            args.method,
+            &args.args[..],
+            args.attached_balance,
+            args.attached_gas,
+        )
+    }
+
+    fn promise_attach_callback(
+        &mut self,
+        base: PromiseId,
+        callback: &PromiseCreateArgs,
+    ) -> PromiseId {
+        sdk::promise_then(
+            base,
+            callback.target_account_id.clone(),
+            callback.method,
+            &callback.args[..],
+            callback.attached_balance,
+            callback.attached_gas,
+        )
+    }
+
+    fn promise_and(&mut self, promises: &[PromiseId]) -> PromiseId {
+        sdk::promise_and(promises)
+    }
+
+    fn promise_transfer(&mut self, account_id: AccountId, amount: Balance) -> PromiseId {
+        let promise0 = sdk::promise_batch_create(account_id);
+        sdk::promise_batch_action_transfer(promise0, amount);
+        promise0
+    }
+
+    fn promise_return(&mut self, promise: PromiseId) {
+        sdk::promise_return(promise)
+    }
+
+    fn value_return(&mut self, value: &[u8]) {
+        sdk::value_return(value)
+    }
+}
+
+/// Dispatcher recording the calls instead of scheduling them and serving canned
+/// results to the callback, so a deposit can be finished within a single call.
+#[cfg(feature = "integration-test")]
+#[derive(Default)]
+pub struct MockPromiseHandler {
+    /// Results served to the callback, in the order of the joined promises.
+    pub results: Vec<PromiseResult>,
+    /// Created calls and callbacks, a promise id is the index of its call.
+    pub calls: Vec<PromiseCreateArgs>,
+    /// Transfers as `(account_id, amount)`, they don't take a promise id.
+    pub transfers: Vec<(AccountId, Balance)>,
+    pub returned_promise: Option<PromiseId>,
+    pub returned_value: Option<Vec<u8>>,
+}
+
+#[cfg(feature = "integration-test")]
+impl MockPromiseHandler {
+    /// Last call scheduled with the given method.
+    pub fn find_call(&self, method: &[u8]) -> Option<&PromiseCreateArgs> {
+        self.calls.iter().rev().find(|call| call.method == method)
+    }
+}
+
+#[cfg(feature = "integration-test")]
+impl PromiseHandler for MockPromiseHandler {
+    fn promise_results_count(&self) -> u64 {
+        self.results.len() as u64
+    }
+
+    fn promise_result(&self, index: u64) -> PromiseResult {
+        self.results
+            .get(index as usize)
+            .cloned()
+            .unwrap_or(PromiseResult::NotReady)
+    }
+
+    fn promise_create_call(&mut self, args: &PromiseCreateArgs) -> PromiseId {
+        self.calls.push(args.clone());
+        self.calls.len() as PromiseId - 1
+    }
+
+    fn promise_attach_callback(
+        &mut self,
+        _base: PromiseId,
+        callback: &PromiseCreateArgs,
+    ) -> PromiseId {
+        self.promise_create_call(callback)
+    }
+
+    fn promise_and(&mut self, promises: &[PromiseId]) -> PromiseId {
+        // Joined promises keep the id of the first one
+        promises[0]
+    }
+
+    fn promise_transfer(&mut self, account_id: AccountId, amount: Balance) -> PromiseId {
+        self.transfers.push((account_id, amount));
+        PromiseId::MAX
+    }
+
+    fn promise_return(&mut self, promise: PromiseId) {
+        self.returned_promise = Some(promise);
+    }
+
+    fn value_return(&mut self, value: &[u8]) {
+        self.returned_value = Some(value.to_vec());
+    }
+}
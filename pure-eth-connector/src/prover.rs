@@ -1,15 +1,62 @@
 use super::*;
+use core::convert::TryFrom;
 use ethabi::{Event, EventParam, Hash, Log, ParamType, RawLog};
 
-pub type EthAddress = [u8; 20];
+/// Etherium address
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, BorshDeserialize, BorshSerialize)]
+pub struct EthAddress(pub RawAddress);
 
-/// Validate Etherium address from string and return EthAddress
-pub fn validate_eth_address(address: String) -> EthAddress {
-    let data = hex::decode(address).expect("ETH address should be a valid hex string.");
-    assert_eq!(data.len(), 20, "ETH address should be 20 bytes long");
-    let mut result = [0u8; 20];
-    result.copy_from_slice(&data);
-    result
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValidateEthAddressError {
+    InvalidHex,
+    InvalidLength,
+    InvalidChecksum,
+}
+
+impl AsRef<[u8]> for ValidateEthAddressError {
+    fn as_ref(&self) -> &[u8] {
+        match self {
+            Self::InvalidHex => b"ETH address should be a valid hex string.",
+            Self::InvalidLength => b"ETH address should be 20 bytes long",
+            Self::InvalidChecksum => b"ETH address has an invalid EIP-55 checksum",
+        }
+    }
+}
+
+/// Validate Etherium address from hex string, with an optional `0x` prefix.
+/// Mixed case addresses must carry a valid EIP-55 checksum.
+impl TryFrom<&str> for EthAddress {
+    type Error = ValidateEthAddressError;
+
+    fn try_from(address: &str) -> Result<Self, Self::Error> {
+        let address = address.strip_prefix("0x").unwrap_or(address);
+        let data = hex::decode(address).map_err(|_| ValidateEthAddressError::InvalidHex)?;
+        if data.len() != 20 {
+            return Err(ValidateEthAddressError::InvalidLength);
+        }
+        let is_mixed_case = address.chars().any(|c| c.is_ascii_lowercase())
+            && address.chars().any(|c| c.is_ascii_uppercase());
+        if is_mixed_case && !is_valid_checksum(address) {
+            return Err(ValidateEthAddressError::InvalidChecksum);
+        }
+        let mut result = [0u8; 20];
+        result.copy_from_slice(&data);
+        Ok(Self(result))
+    }
+}
+
+/// EIP-55: a hex letter is uppercase when the matching nibble of the keccak256 hash
+/// of the lowercase address is 8 or more.
+fn is_valid_checksum(address: &str) -> bool {
+    let hash = sdk::keccak(address.to_ascii_lowercase().as_bytes());
+    address.chars().enumerate().all(|(i, c)| {
+        let nibble = if i % 2 == 0 {
+            hash.0[i / 2] >> 4
+        } else {
+            hash.0[i / 2] & 0x0f
+        };
+        !c.is_ascii_alphabetic() || c.is_ascii_uppercase() == (nibble >= 8)
+    })
 }
 
 #[derive(Default, BorshDeserialize, BorshSerialize, Clone)]
@@ -23,8 +70,55 @@ pub struct Proof {
     pub skip_bridge_call: bool,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProofError {
+    EmptyLogEntry,
+    EmptyReceipt,
+    EmptyHeader,
+    EmptyProof,
+    InvalidLogEntry,
+    InvalidHeader,
+    InvalidReceipt,
+    FailedReceipt,
+    LogEntryNotInReceipt,
+}
+
+impl AsRef<[u8]> for ProofError {
+    fn as_ref(&self) -> &[u8] {
+        match self {
+            Self::EmptyLogEntry => b"Proof log entry data is empty",
+            Self::EmptyReceipt => b"Proof receipt data is empty",
+            Self::EmptyHeader => b"Proof header data is empty",
+            Self::EmptyProof => b"Proof path is empty",
+            Self::InvalidLogEntry => b"Proof log entry is not a valid RLP log",
+            Self::InvalidHeader => b"Proof header is not a valid RLP block header",
+            Self::InvalidReceipt => b"Proof receipt is not a valid RLP receipt",
+            Self::FailedReceipt => b"Proof receipt is not successful",
+            Self::LogEntryNotInReceipt => b"Proof log entry is not part of the receipt",
+        }
+    }
+}
+
 impl Proof {
+    /// Canonical hash identifying the proven event, used to prevent its reuse: sha256 of
+    /// the block hash, then the receipt and log indexes as little-endian u64.
+    pub fn hash(&self) -> RawH256 {
+        let mut data = keccak(&self.header_data).0.to_vec();
+        data.extend(self.receipt_index.try_to_vec().unwrap());
+        data.extend(self.log_index.try_to_vec().unwrap());
+        let mut result = [0u8; 32];
+        result.copy_from_slice(&sdk::sha256(&data[..]));
+        result
+    }
+
+    /// Key of the proof in the used events storage, the hex encoded `hash`.
     pub fn get_key(&self) -> String {
+        hex::encode(self.hash())
+    }
+
+    /// Key of the proof in used events recorded before keys were derived from the
+    /// block hash: decimal bytes of the sha256 of the indexes and the full header.
+    pub fn legacy_key(&self) -> String {
         let mut data = self.log_index.try_to_vec().unwrap();
         data.extend(self.receipt_index.try_to_vec().unwrap());
         data.extend(self.header_data.clone());
@@ -33,6 +127,73 @@ impl Proof {
             .map(|n| n.to_string())
             .collect()
     }
+
+    /// Number of the Ethereum block containing the proven receipt, decoded from the header.
+    pub fn block_number(&self) -> Result<u64, ProofError> {
+        // Header fields: parent_hash, uncles_hash, author, state_root, transactions_root,
+        // receipts_root, log_bloom, difficulty, number, ...
+        rlp::Rlp::new(&self.header_data)
+            .val_at::<u64>(8)
+            .map_err(|_| ProofError::InvalidHeader)
+    }
+
+    /// Cross-check that the receipt is successful and contains the proven log entry.
+    pub fn validate_receipt(&self) -> Result<(), ProofError> {
+        let data = match self.receipt_data.first() {
+            // EIP-2718 typed receipts are prefixed with the transaction type
+            Some(&tx_type) if tx_type < 0x80 => &self.receipt_data[1..],
+            _ => &self.receipt_data[..],
+        };
+        // Receipt fields: status, cumulative_gas_used, log_bloom, logs
+        let receipt = rlp::Rlp::new(data);
+        let status: u8 = receipt.val_at(0).map_err(|_| ProofError::InvalidReceipt)?;
+        if status != 1 {
+            return Err(ProofError::FailedReceipt);
+        }
+        let logs = receipt.at(3).map_err(|_| ProofError::InvalidReceipt)?;
+        if !logs
+            .iter()
+            .any(|log| log.as_raw() == &self.log_entry_data[..])
+        {
+            return Err(ProofError::LogEntryNotInReceipt);
+        }
+        Ok(())
+    }
+
+    /// Structural checks which don't require the prover.
+    pub fn validate(&self) -> Result<(), ProofError> {
+        if self.log_entry_data.is_empty() {
+            return Err(ProofError::EmptyLogEntry);
+        }
+        if self.receipt_data.is_empty() {
+            return Err(ProofError::EmptyReceipt);
+        }
+        if self.header_data.is_empty() {
+            return Err(ProofError::EmptyHeader);
+        }
+        if self.proof.is_empty() || self.proof.iter().any(|node| node.is_empty()) {
+            return Err(ProofError::EmptyProof);
+        }
+        rlp::decode::<LogEntry>(&self.log_entry_data).map_err(|_| ProofError::InvalidLogEntry)?;
+        self.block_number()?;
+        Ok(())
+    }
+}
+
+/// Versioned wire format of the deposit proof, so new layouts can be introduced
+/// while relayers submitting older ones keep working.
+#[derive(BorshDeserialize, BorshSerialize, Clone)]
+pub enum VersionedProof {
+    V1(Proof),
+}
+
+impl VersionedProof {
+    /// Proof in the layout expected by the prover.
+    pub fn proof(&self) -> &Proof {
+        match self {
+            Self::V1(proof) => proof,
+        }
+    }
 }
 
 /// Parameters of Etherium event
@@ -45,9 +206,9 @@ pub struct EthEvent {
 }
 
 impl EthEvent {
-    /// Get Etherium event from `log_entry_data`
-    pub fn fetch_log_entry_data(name: &str, params: EthEventParams, data: &[u8]) -> Self {
-        let event = Event {
+    /// Build ABI description of Etherium event
+    pub fn event(name: &str, params: EthEventParams) -> Event {
+        Event {
             name: name.to_string(),
             inputs: params
                 .into_iter()
@@ -58,21 +219,42 @@ impl EthEvent {
                 })
                 .collect(),
             anonymous: false,
-        };
-        let log_entry: LogEntry = rlp::decode(data).expect("Invalid RLP");
-        let eth_custodian_address = log_entry.address.0;
-        let topics = log_entry.topics.iter().map(|h| Hash::from(h.0)).collect();
+        }
+    }
+
+    /// Get Etherium event from `log_entry_data`, identified by its expected `topic`
+    /// rather than the signature derived from `name`.
+    pub fn fetch_log_entry_data(
+        name: &str,
+        params: EthEventParams,
+        topic: &RawH256,
+        data: &[u8],
+    ) -> Result<Self, ConnectorError> {
+        let mut event = Self::event(name, params);
+        let log_entry: LogEntry = rlp::decode(data).map_err(|_| ConnectorError::InvalidLogEntry)?;
+        let eth_custodian_address = EthAddress(log_entry.address.0);
+        if log_entry.topics.first().map(|h| &h.0) != Some(topic) {
+            return Err(ConnectorError::UnexpectedEventTopic);
+        }
+        // Topic0 is checked against the registry, the rest is parsed as an anonymous event
+        event.anonymous = true;
+        let topics = log_entry.topics[1..]
+            .iter()
+            .map(|h| Hash::from(h.0))
+            .collect();
 
         let raw_log = RawLog {
             topics,
             data: log_entry.data,
         };
-        let log = event.parse_log(raw_log).expect("Failed to parse event log");
+        let log = event
+            .parse_log(raw_log)
+            .map_err(|_| ConnectorError::InvalidLogEntry)?;
 
-        Self {
+        Ok(Self {
             eth_custodian_address,
             log,
-        }
+        })
     }
 }
 
@@ -108,3 +290,13 @@ impl From<json::JsonValue> for Proof {
         }
     }
 }
+
+impl From<json::JsonValue> for VersionedProof {
+    fn from(v: json::JsonValue) -> Self {
+        // Relayers predating versioning don't send the `version` field
+        match v.u64("version").unwrap_or(1) {
+            1 => Self::V1(Proof::from(v)),
+            _ => sdk::panic_utf8(ConnectorError::UnsupportedProofVersion.as_ref()),
+        }
+    }
+}
@@ -361,6 +361,10 @@ pub fn promise_then(
     }
 }
 
+pub fn promise_and(promise_indices: &[u64]) -> u64 {
+    unsafe { exports::promise_and(promise_indices.as_ptr() as _, promise_indices.len() as _) }
+}
+
 pub fn promise_return(promise_idx: u64) {
     unsafe {
         exports::promise_return(promise_idx);
@@ -432,6 +436,36 @@ pub fn promise_batch_create(account_id: AccountId) -> u64 {
     unsafe { exports::promise_batch_create(account_id.len() as _, account_id.as_ptr() as _) }
 }
 
+pub fn promise_batch_action_deploy_contract(promise_index: u64, code: &[u8]) {
+    unsafe {
+        exports::promise_batch_action_deploy_contract(
+            promise_index,
+            code.len() as _,
+            code.as_ptr() as _,
+        );
+    }
+}
+
+pub fn promise_batch_action_function_call(
+    promise_index: u64,
+    method_name: &[u8],
+    arguments: &[u8],
+    amount: Balance,
+    gas: Gas,
+) {
+    unsafe {
+        exports::promise_batch_action_function_call(
+            promise_index,
+            method_name.len() as _,
+            method_name.as_ptr() as _,
+            arguments.len() as _,
+            arguments.as_ptr() as _,
+            &amount as *const Balance as _,
+            gas,
+        );
+    }
+}
+
 pub fn sha256(value: &[u8]) -> Vec<u8> {
     unsafe {
         exports::sha256(value.len() as _, value.as_ptr() as _, 0);
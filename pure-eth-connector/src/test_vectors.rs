@@ -0,0 +1,77 @@
+use super::*;
+use primitive_types::U128;
+
+/// Fixed input and the encoding this build produces for it.
+#[derive(BorshSerialize)]
+pub struct TestVector {
+    pub name: String,
+    /// JSON description of the input.
+    pub input: String,
+    /// Hex encoded bytes, or the key itself for `proof_key`.
+    pub output: String,
+}
+
+/// Canonical vectors for third-party custodians and relayers to check their encodings
+/// against. Outputs are computed by the same code paths as deposits and withdrawals.
+pub fn test_vectors() -> Vec<TestVector> {
+    vec![deposited_event(), withdraw_result(), proof_key()]
+}
+
+/// `log_entry_data` of a `DepositedToNear` event with the default layout.
+fn deposited_event() -> TestVector {
+    let event = EthDepositedEvent {
+        eth_custodian_address: EthAddress([0x11; 20]),
+        sender: hex::encode([0x22; 20]),
+        recipient: "alice.near".into(),
+        message: Some("bob.near".into()),
+        amount: U128::from(1_000_000u64),
+        fee: U128::from(1_000u64),
+    };
+    TestVector {
+        name: "deposited_event".into(),
+        input: format!(
+            r#"{{"eth_custodian_address":"{}","sender":"{}","recipient":"alice.near:bob.near","amount":"1000000","fee":"1000"}}"#,
+            hex::encode(event.eth_custodian_address.0),
+            event.sender,
+        ),
+        output: hex::encode(event.to_log_entry_data(&EventLayout::default())),
+    }
+}
+
+/// Borsh `WithdrawResult` returned by `withdraw` and proven on Ethereum.
+fn withdraw_result() -> TestVector {
+    let result = WithdrawResult {
+        amount: 999_000,
+        recipient_id: [0x33; 20],
+        eth_custodian_address: [0x11; 20],
+        memo: [0x44; 32],
+    };
+    TestVector {
+        name: "withdraw_result".into(),
+        input: format!(
+            r#"{{"amount":"999000","recipient_id":"{}","eth_custodian_address":"{}","memo":"{}"}}"#,
+            hex::encode(result.recipient_id),
+            hex::encode(result.eth_custodian_address),
+            hex::encode(result.memo),
+        ),
+        output: hex::encode(result.try_to_vec().unwrap()),
+    }
+}
+
+/// Key of a proof in the used events storage, as taken by `is_used_event`.
+fn proof_key() -> TestVector {
+    let proof = Proof {
+        log_index: 1,
+        log_entry_data: vec![0x01, 0x02],
+        receipt_index: 2,
+        receipt_data: vec![0x03],
+        header_data: vec![0x04],
+        proof: vec![vec![0x05, 0x06]],
+        skip_bridge_call: false,
+    };
+    TestVector {
+        name: "proof_key".into(),
+        input: r#"{"log_index":1,"log_entry_data":"0102","receipt_index":2,"receipt_data":"03","header_data":"04","proof":["0506"],"skip_bridge_call":false}"#.into(),
+        output: proof.get_key(),
+    }
+}
@@ -35,6 +35,16 @@ pub struct Log {
 pub struct InitCallArgs {
     pub prover_account: AccountId,
     pub eth_custodian_address: AccountId,
+    pub owner_id: Option<AccountId>,
+    /// Initial fee configuration, zero when not given.
+    pub relayer_fee_share_bps: Option<u16>,
+    pub deposit_fee_bps: Option<u16>,
+    pub withdraw_fee_bps: Option<u16>,
+    pub min_withdraw_amount: Option<Balance>,
+}
+
+pub struct OwnerCallArgs {
+    pub owner_id: AccountId,
 }
 
 #[derive(Debug)]
@@ -49,10 +59,348 @@ pub struct TransferCallArgs {
     pub memo: Option<String>,
 }
 
+/// `EthConnector` layout of state version 1.
+#[derive(BorshDeserialize)]
+pub struct EthConnectorV1 {
+    pub prover_account: AccountId,
+    pub eth_custodian_address: EthAddress,
+}
+
 #[derive(Debug, BorshDeserialize, BorshSerialize)]
 pub struct EthConnector {
+    /// Account allowed to call the admin methods.
+    pub owner_id: AccountId,
+    /// Owner proposed by `propose_owner`, waiting for `accept_owner`.
+    pub proposed_owner_id: Option<AccountId>,
     pub prover_account: AccountId,
     pub eth_custodian_address: EthAddress,
+    pub deposited_event_layout: EventLayout,
+    pub paused_mask: PausedMask,
+    /// Engine contract crediting balances for deposits addressed to it.
+    pub engine_account: Option<AccountId>,
+    /// Share of the deposit fee paid to the relayer, in basis points.
+    pub relayer_fee_share_bps: u16,
+    /// Admin parameter changes waiting for their activation time.
+    pub scheduled_changes: Vec<ScheduledParamChange>,
+    /// Banner shown while the contract is in read-only maintenance mode.
+    pub maintenance_banner: Option<String>,
+    /// Highest Ethereum block height among accepted deposit proofs.
+    pub last_eth_block_height: u64,
+    /// Code hash of the audited reproducible build of the deployed wasm.
+    pub expected_code_hash: Option<RawH256>,
+    /// Topic0 hashes identifying the custodian events accepted in proofs.
+    pub event_topics: EventTopics,
+    /// Running totals of tokens minted for deposits and burned for withdrawals,
+    /// checked against the total supply by `reconcile`.
+    pub total_minted: Balance,
+    pub total_burned: Balance,
+    /// Protocol fee charged on deposits, in basis points.
+    pub deposit_fee_bps: u16,
+    /// Protocol fees collected by deposits and withdrawals and not yet claimed.
+    pub accrued_fees: Balance,
+    /// Fee charged on withdrawals, in basis points.
+    pub withdraw_fee_bps: u16,
+    pub min_withdraw_amount: Balance,
+    /// Parameters permanently locked by `irreversibly_freeze`.
+    pub frozen_mask: FrozenMask,
+    /// Prover called in shadow mode next to `prover_account`.
+    pub shadow_prover: Option<ShadowProver>,
+    /// Measured size of a canonical used proof record, charged to depositors.
+    pub used_proof_storage_bytes: Option<u64>,
+    /// Bridge registry the connector reports itself to with `register_self`.
+    pub registry: Option<RegistryConfig>,
+}
+
+impl EthConnector {
+    /// Short hash of the parameters deposits and withdrawals are processed under,
+    /// letting indexers correlate each operation with the active configuration.
+    pub fn config_hash(&self) -> String {
+        let mut data = self.prover_account.try_to_vec().unwrap();
+        data.extend(self.eth_custodian_address.try_to_vec().unwrap());
+        data.extend(self.deposited_event_layout.try_to_vec().unwrap());
+        data.extend(self.event_topics.try_to_vec().unwrap());
+        data.extend(self.engine_account.try_to_vec().unwrap());
+        data.extend(self.relayer_fee_share_bps.try_to_vec().unwrap());
+        data.extend(self.deposit_fee_bps.try_to_vec().unwrap());
+        data.extend(self.withdraw_fee_bps.try_to_vec().unwrap());
+        data.extend(self.min_withdraw_amount.try_to_vec().unwrap());
+        hex::encode(&sdk::sha256(&data[..])[..8])
+    }
+
+    /// Insert a change keeping the queue ordered by activation time.
+    pub fn schedule_change(&mut self, scheduled: ScheduledParamChange) {
+        let index = self
+            .scheduled_changes
+            .iter()
+            .position(|item| item.effective_at > scheduled.effective_at)
+            .unwrap_or(self.scheduled_changes.len());
+        self.scheduled_changes.insert(index, scheduled);
+    }
+
+    /// Apply every scheduled change whose activation time has passed.
+    pub fn apply_scheduled_changes(&mut self, now: u64) {
+        let due = self
+            .scheduled_changes
+            .iter()
+            .take_while(|item| item.effective_at <= now)
+            .count();
+        for scheduled in self.scheduled_changes.drain(..due) {
+            match scheduled.change {
+                ParamChange::RelayerFeeShare(bps) => self.relayer_fee_share_bps = bps,
+                ParamChange::DepositedEventTopic(topic) => self.event_topics.deposited = topic,
+                ParamChange::DepositFee(bps) => self.deposit_fee_bps = bps,
+                ParamChange::WithdrawFee(bps) => self.withdraw_fee_bps = bps,
+                ParamChange::MinWithdrawAmount(amount) => self.min_withdraw_amount = amount,
+            }
+        }
+    }
+}
+
+/// Prover verifying deposit proofs in shadow mode, with its results compared
+/// to the ones of the primary prover.
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct ShadowProver {
+    pub account_id: AccountId,
+    pub agreements: u64,
+    pub disagreements: u64,
+    /// Calls which failed or returned an unexpected result.
+    pub failures: u64,
+}
+
+impl ShadowProver {
+    pub fn new(account_id: AccountId) -> Self {
+        Self {
+            account_id,
+            agreements: 0,
+            disagreements: 0,
+            failures: 0,
+        }
+    }
+}
+
+/// Supply accounting checked by `reconcile`.
+#[derive(BorshSerialize)]
+pub struct ReconcileReport {
+    pub total_minted: Balance,
+    pub total_burned: Balance,
+    pub total_supply: Balance,
+    pub consistent: bool,
+}
+
+/// Pending operator work returned by `get_operator_tasks`.
+#[derive(BorshSerialize)]
+pub struct OperatorTasks {
+    /// Deposits to submit again.
+    pub stuck_submissions: Vec<StuckSubmission>,
+    /// Accrued protocol fees waiting for `claim_fees`.
+    pub claimable_fees: Balance,
+    pub supply_consistent: bool,
+    pub storage_covered: bool,
+    pub shadow_prover_disagreements: u64,
+}
+
+/// Failures of the deposit and withdraw entry points, reported as stable
+/// machine-readable codes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConnectorError {
+    WrongEventAddress,
+    NotEnoughBalanceForFee,
+    UnexpectedPromiseResults,
+    ProofVerificationFailed,
+    ProofAlreadyUsed,
+    BlockedEthRecipient,
+    AllowanceExceeded,
+    AmountOverflow,
+    WithdrawBelowMinimum,
+    InsufficientStorageDeposit,
+    ProofTooOld,
+    InvalidLogEntry,
+    UnexpectedEventTopic,
+    InvalidRecipient,
+    Paused,
+    Maintenance,
+    UnregisteredAlias,
+    AliasRegisteredLate,
+    UnsupportedProofVersion,
+    InvalidInput,
+}
+
+impl AsRef<[u8]> for ConnectorError {
+    fn as_ref(&self) -> &[u8] {
+        match self {
+            Self::WrongEventAddress => b"ERR_WRONG_EVENT_ADDRESS",
+            Self::NotEnoughBalanceForFee => b"ERR_NOT_ENOUGH_BALANCE_FOR_FEE",
+            Self::UnexpectedPromiseResults => b"ERR_PROMISE_COUNT",
+            Self::ProofVerificationFailed => b"ERR_VERIFY_PROOF",
+            Self::ProofAlreadyUsed => b"ERR_PROOF_EXIST",
+            Self::BlockedEthRecipient => b"ERR_BLOCKED_ETH_RECIPIENT",
+            Self::AllowanceExceeded => b"ERR_ALLOWANCE_EXCEEDED",
+            Self::AmountOverflow => b"ERR_AMOUNT_OVERFLOW",
+            Self::WithdrawBelowMinimum => b"ERR_WITHDRAW_BELOW_MINIMUM",
+            Self::InsufficientStorageDeposit => b"ERR_INSUFFICIENT_STORAGE_DEPOSIT",
+            Self::ProofTooOld => b"ERR_PROOF_TOO_OLD",
+            Self::InvalidLogEntry => b"ERR_INVALID_LOG_ENTRY",
+            Self::UnexpectedEventTopic => b"ERR_UNEXPECTED_EVENT_TOPIC",
+            Self::InvalidRecipient => b"ERR_INVALID_RECIPIENT",
+            Self::Paused => b"ERR_PAUSED",
+            Self::Maintenance => b"ERR_MAINTENANCE",
+            Self::UnregisteredAlias => b"ERR_UNREGISTERED_ALIAS",
+            Self::AliasRegisteredLate => b"ERR_ALIAS_REGISTERED_LATE",
+            Self::UnsupportedProofVersion => b"ERR_UNSUPPORTED_PROOF_VERSION",
+            Self::InvalidInput => b"ERR_INVALID_INPUT",
+        }
+    }
+}
+
+/// Admin parameter change taking effect at `effective_at` (block timestamp in nanoseconds).
+#[derive(BorshSerialize, BorshDeserialize, Clone)]
+pub struct ScheduledParamChange {
+    pub effective_at: u64,
+    pub change: ParamChange,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone)]
+pub enum ParamChange {
+    RelayerFeeShare(u16),
+    DepositedEventTopic(RawH256),
+    DepositFee(u16),
+    WithdrawFee(u16),
+    MinWithdrawAmount(Balance),
+}
+
+impl ParamChange {
+    /// Freeze flag locking the changed parameter.
+    pub fn frozen_by(&self) -> FrozenMask {
+        match self {
+            Self::RelayerFeeShare(_)
+            | Self::DepositFee(_)
+            | Self::WithdrawFee(_)
+            | Self::MinWithdrawAmount(_) => FREEZE_FEES,
+            Self::DepositedEventTopic(_) => FREEZE_EVENT_FORMAT,
+        }
+    }
+}
+
+pub type FrozenMask = u8;
+
+/// Layout and topics of the custodian events accepted in proofs.
+pub const FREEZE_EVENT_FORMAT: FrozenMask = 1 << 0;
+pub const FREEZE_ENGINE_ACCOUNT: FrozenMask = 1 << 1;
+/// Relayer fee share, deposit fee and withdrawal fee parameters.
+pub const FREEZE_FEES: FrozenMask = 1 << 2;
+pub const FREEZE_CUSTODIAN: FrozenMask = 1 << 3;
+pub const FREEZE_PROVER: FrozenMask = 1 << 4;
+
+/// Custodian replaced by `set_eth_custodian_address`.
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct RetiredCustodian {
+    pub eth_custodian_address: EthAddress,
+    /// NEAR block height of the replacement.
+    pub retired_at: u64,
+    /// Proofs of deposits to this custodian are still accepted up to this Ethereum block.
+    pub accepted_until_eth_block: Option<u64>,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone)]
+pub struct SetEthCustodianAddressCallArgs {
+    pub eth_custodian_address: EthAddress,
+    /// Ethereum blocks past the highest accepted proof during which the replaced
+    /// custodian stays accepted.
+    pub grace_blocks: Option<u64>,
+    /// Last Ethereum block of the grace period, instead of `grace_blocks`. The highest
+    /// accepted proof may be stale after a quiet period.
+    pub grace_until_eth_block: Option<u64>,
+}
+
+/// Code and state layout versions running from `block_height` on.
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct SchemaLogEntry {
+    pub contract_version: String,
+    pub state_version: u32,
+    pub block_height: u64,
+}
+
+/// Admin operation going through the timelock.
+#[derive(BorshSerialize, BorshDeserialize, Clone)]
+pub enum AdminAction {
+    SetEthCustodianAddress(SetEthCustodianAddressCallArgs),
+    SetProverAccount(AccountId),
+    SetRelayerFeeShare(u16),
+    SetDepositFee(u16),
+    SetWithdrawFee {
+        withdraw_fee_bps: u16,
+        min_withdraw_amount: Balance,
+    },
+    /// Lets `upgrade` deploy the code with this sha256 hash.
+    Upgrade(RawH256),
+    SetTimelockDelay(u64),
+    SetDepositedEventLayout(EventLayout),
+    SetEngineAccount(Option<AccountId>),
+    ScheduleDepositedEventTopic(ScheduleEventTopicCallArgs),
+    RevokeProof(RevokeProofCallArgs),
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone)]
+pub struct StagedAction {
+    pub id: u64,
+    pub action: AdminAction,
+    /// Block timestamp from which the action can be executed, in nanoseconds.
+    pub unlock_at: u64,
+}
+
+/// Delay and pending actions of the admin timelock, disabled while the delay is zero.
+#[derive(BorshSerialize, BorshDeserialize, Default)]
+pub struct Timelock {
+    pub delay: u64,
+    pub next_id: u64,
+    pub actions: Vec<StagedAction>,
+}
+
+pub type RefusalMask = u8;
+
+/// Guard classes answering with a `GuardRefusal` instead of panicking when set
+/// in the refusal policy.
+pub const REFUSE_PAUSED: RefusalMask = 1 << 0;
+/// Withdrawal allowance and minimum withdrawal amount.
+pub const REFUSE_LIMIT_EXCEEDED: RefusalMask = 1 << 1;
+pub const REFUSE_BLOCKLISTED: RefusalMask = 1 << 2;
+
+/// Returned in place of the call result when a guard refuses the call, after the
+/// attached deposit was refunded.
+#[derive(BorshSerialize)]
+pub struct GuardRefusal {
+    pub guard: RefusalMask,
+    pub error: String,
+}
+
+/// Registry of the topic0 hashes expected for each supported custodian event,
+/// so a custodian upgrade renaming an event doesn't require a new connector build.
+#[derive(Debug, Clone, PartialEq, BorshDeserialize, BorshSerialize)]
+pub struct EventTopics {
+    pub deposited: RawH256,
+}
+
+impl Default for EventTopics {
+    fn default() -> Self {
+        Self {
+            deposited: EthDepositedEvent::signature_topic(),
+        }
+    }
+}
+
+/// Flags marking which `Deposited` event params are indexed by the custodian contract,
+/// in the order: sender, recipient, amount, fee.
+#[derive(Debug, Clone, PartialEq, BorshDeserialize, BorshSerialize)]
+pub struct EventLayout {
+    pub indexed: Vec<bool>,
+}
+
+impl Default for EventLayout {
+    fn default() -> Self {
+        Self {
+            indexed: vec![true, false, false, false],
+        }
+    }
 }
 
 #[derive(BorshSerialize, BorshDeserialize)]
@@ -60,12 +408,247 @@ pub struct FinishDepositCallArgs {
     pub new_owner_id: AccountId,
     pub amount: Balance,
     pub fee: Balance,
-    pub proof: Proof,
+    pub proof: VersionedProof,
+    pub msg: Option<String>,
+    pub relayer_id: AccountId,
+    /// Prover which verified the proof.
+    pub prover_account: AccountId,
+    /// Light client blocks required on top of the proof block. When non-zero the
+    /// light client height is the last promise result.
+    pub min_block_confirmations: u64,
+    pub eth_tx_hash: Option<RawH256>,
+    pub skip_used: bool,
+    /// Ethereum sender of the deposit, hex encoded.
+    pub sender: String,
+    pub trace_id: String,
+    /// Storage deposit charged by `deposit`, refunded if the proof block isn't confirmed yet.
+    pub storage_deposit: Balance,
+}
+
+/// What an engine deposit minted, undone by `finish_engine_deposit` when the
+/// engine fails to credit the recipient.
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct FinishEngineDepositCallArgs {
+    pub proof_key: String,
+    /// Index of the used proof record in the pruning index.
+    pub used_event_index: u64,
+    pub trace_id: String,
+    pub engine_account_id: AccountId,
+    pub amount: Balance,
+    pub treasury_id: AccountId,
+    pub treasury_fee: Balance,
+    pub relayer_id: AccountId,
+    pub relayer_fee: Balance,
+    pub protocol_fee: Balance,
+    /// Ethereum sender and timestamp of the history entry pushed for the deposit.
+    pub sender: String,
+    pub minted_at: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, PartialEq)]
+pub enum HistoryKind {
+    Deposit,
+    Withdraw,
+}
+
+/// Bridge transfer of an account, kept for wallets to display.
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct HistoryEntry {
+    pub kind: HistoryKind,
+    pub amount: Balance,
+    pub fee: Balance,
+    /// Ethereum sender of a deposit or recipient of a withdrawal, hex encoded.
+    pub eth_address: String,
+    pub timestamp: u64,
+}
+
+pub struct GetAccountHistoryCallArgs {
+    pub account_id: AccountId,
+    pub from_index: u64,
+    pub limit: u64,
+}
+
+/// Reason a deposit submission would fail, reported by `preflight`.
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub enum PreflightFailure {
+    Maintenance,
+    DepositPaused,
+    InvalidProof(String),
+    ProofAlreadyUsed,
+    CustodianMismatch,
+    FeeExceedsAmount,
+    ProofTooOld,
+    /// The log entry can't be parsed as a deposit event, with the error code.
+    InvalidEvent(String),
+    /// The `@<alias>` recipient isn't registered.
+    UnregisteredAlias,
+    /// The `@<alias>` recipient was registered at or after the proof block.
+    AliasRegisteredLate,
+}
+
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct PreflightReport {
+    /// Empty when the submission is expected to succeed.
+    pub failures: Vec<PreflightFailure>,
+    /// Gas to attach on top of the `deposit` call itself.
+    pub required_gas: Gas,
+}
+
+/// Outcome of `deposit_if_unused`.
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub enum DepositStatus {
+    Deposited,
+    AlreadyUsed,
+    /// The prover call didn't succeed, the proof can be submitted again.
+    Retryable,
+    /// Verified but not minted for lack of gas, finished with `complete_deposit`.
+    Deferred,
+    /// Too few light client blocks on top of the proof block yet. The proof can be
+    /// submitted again and the storage deposit was refunded.
+    NotConfirmed,
+}
+
+/// Deposit whose prover call failed or ran out of gas.
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct StuckSubmission {
+    pub proof_key: String,
+    pub new_owner_id: AccountId,
+    pub amount: Balance,
+    pub relayer_id: AccountId,
+    pub timestamp: u64,
+    pub trace_id: String,
+}
+
+pub struct ProofKeyCallArgs {
+    pub proof_key: String,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone)]
+pub struct RevokeProofCallArgs {
+    pub proof_key: String,
+    pub evidence: String,
+    /// Account holding the tokens minted for the proof, to burn them from.
+    pub clawback_account_id: Option<AccountId>,
+    pub clawback_amount: Balance,
+}
+
+/// Accepted proof whose Ethereum block was orphaned by a reorg.
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct RevokedProof {
+    pub evidence: String,
+    pub clawback_account_id: Option<AccountId>,
+    /// Part of the requested amount which was actually burned.
+    pub clawed_back: Balance,
+    pub timestamp: u64,
+}
+
+/// Finalized deposit, indexed by its trace id and its proof key.
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct DepositReceipt {
+    pub proof_key: String,
+    pub new_owner_id: AccountId,
+    pub amount: Balance,
+    pub fee: Balance,
+    pub relayer_id: AccountId,
+    pub relayer_fee: Balance,
+    pub protocol_fee: Balance,
+    pub eth_block_height: u64,
+    pub trace_id: String,
+    /// Gas allotted to the prover call and to the `finish_deposit` callback.
+    pub prover_gas: Gas,
+    pub finish_deposit_gas: Gas,
+    /// Ethereum transaction hash given by the submitter. Unverified: the proof doesn't
+    /// commit to it.
+    pub eth_tx_hash: Option<RawH256>,
+}
+
+/// Gas allotted to each downstream call, returned by `get_gas_budget`.
+#[derive(BorshSerialize)]
+pub struct GasBudget {
+    pub verify_log_entry: Gas,
+    pub finish_deposit: Gas,
+    /// Added to `finish_deposit` for deposits credited through the engine.
+    pub ft_on_deposit: Gas,
+    pub finish_engine_deposit: Gas,
+    /// Added to `finish_deposit` for deposits forwarded to the recipient with a message.
+    pub ft_transfer_call: Gas,
+    pub ft_resolve_transfer: Gas,
+}
+
+#[derive(BorshSerialize)]
+pub struct CodeHashExpectation {
+    pub code_hash: Option<RawH256>,
+    pub source_commit: Option<String>,
+    pub contract_version: String,
+}
+
+pub struct SetCodeHashExpectationCallArgs {
+    pub code_hash: RawH256,
+}
+
+pub enum FindDepositCallArgs {
+    TraceId(String),
+    ProofKey(String),
+}
+
+pub struct WithdrawAllowanceCallArgs {
+    pub executor_id: AccountId,
+    pub amount: Balance,
+}
+
+/// Withdrawal pre-authorized by `owner_id`, executed later, possibly in parts, until
+/// `expires_at` (block timestamp in nanoseconds).
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct WithdrawIntent {
+    pub owner_id: AccountId,
+    /// Only this account can execute the intent, any account when unset.
+    pub executor_id: Option<AccountId>,
+    /// Ethereum recipient, hex encoded.
+    pub recipient_id: AccountId,
+    pub remaining_amount: Balance,
+    pub expires_at: u64,
+}
+
+pub struct AuthorizeWithdrawIntentCallArgs {
+    pub executor_id: Option<AccountId>,
+    pub recipient_id: AccountId,
+    pub max_amount: Balance,
+    pub expires_at: u64,
+}
+
+pub struct ExecuteWithdrawIntentCallArgs {
+    pub id: u64,
+    pub amount: Balance,
+    pub memo: Option<RawH256>,
+}
+
+pub struct WithdrawIntentIdCallArgs {
+    pub id: u64,
+}
+
+pub struct WithdrawExecutorCallArgs {
+    pub executor_id: AccountId,
+}
+
+pub struct GetWithdrawAllowanceCallArgs {
+    pub owner_id: AccountId,
+    pub executor_id: AccountId,
 }
 
 pub struct WithdrawCallArgs {
     pub recipient_id: AccountId,
     pub amount: Balance,
+    /// Optional 32-byte memo surfaced in the custodian's `Withdrawn` event.
+    pub memo: Option<RawH256>,
+}
+
+pub struct SetEthRecipientBlockedCallArgs {
+    pub eth_address: EthAddress,
+    pub blocked: bool,
+}
+
+pub struct EthRecipientCallArgs {
+    pub eth_address: EthAddress,
 }
 
 pub struct TransferCallCallArgs {
@@ -95,122 +678,547 @@ pub struct StorageDepositCallArgs {
     pub registration_only: Option<bool>,
 }
 
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct AliasCallArgs {
+    pub alias: String,
+}
+
+/// Registered alias. It only resolves for deposits from Ethereum blocks above
+/// `eth_block_height`, the light client head when it was registered.
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct AliasRecord {
+    pub account_id: AccountId,
+    pub eth_block_height: u64,
+}
+
+/// Routing of bridge events to an off-chain endpoint, read by notifier services.
+/// Only the hash of the URL is kept on-chain.
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct WebhookDescriptor {
+    pub owner_id: AccountId,
+    pub url_hash: RawH256,
+    /// Names of the events to push, all events when empty.
+    pub events: Vec<String>,
+    /// Only push events involving this account.
+    pub account_id: Option<AccountId>,
+}
+
+pub struct RegisterWebhookCallArgs {
+    pub url_hash: RawH256,
+    pub events: Vec<String>,
+    pub account_id: Option<AccountId>,
+}
+
+pub struct UpdateWebhookCallArgs {
+    pub id: u64,
+    pub webhook: RegisterWebhookCallArgs,
+}
+
+pub struct WebhookIdCallArgs {
+    pub id: u64,
+}
+
+pub struct GetWebhooksCallArgs {
+    pub from_index: u64,
+    pub limit: u64,
+}
+
 #[derive(BorshSerialize)]
 pub struct WithdrawResult {
     pub amount: Balance,
     pub recipient_id: RawAddress,
     pub eth_custodian_address: RawAddress,
+    /// Zeroed when the withdrawer didn't supply a memo.
+    pub memo: RawH256,
 }
 
+/// Connector configuration bootstrapped by relayers and UIs in a single view call.
 #[derive(BorshSerialize)]
-pub struct FtOnTransfer {
-    pub amount: Balance,
-    pub msg: String,
-    pub receiver_id: AccountId,
+pub struct CustodianInfo {
+    pub eth_custodian_address: RawAddress,
+    /// Replaced custodians, oldest first.
+    pub custodian_history: Vec<RetiredCustodian>,
+    /// Chain id of the Ethereum network, known once the bridge registry is set.
+    pub eth_chain_id: Option<u64>,
+    pub prover_account: AccountId,
+    /// Blocks behind the highest accepted Ethereum block which are considered final.
+    pub finality_horizon_blocks: u64,
+    pub block_confirmations: Option<BlockConfirmations>,
+    pub contract_version: String,
+    pub state_version: u32,
+    pub paused_mask: PausedMask,
+    pub relayer_fee_share_bps: u16,
 }
 
 #[derive(BorshSerialize)]
-pub struct FtResolveTransfer {
-    pub receiver_id: AccountId,
-    pub amount: Balance,
-    pub current_account_id: AccountId,
+pub struct ConnectorStatus {
+    pub maintenance_banner: Option<String>,
+    pub paused_mask: PausedMask,
+    pub contract_version: String,
+    pub state_version: u32,
+    /// Depth of the scheduled parameter changes queue.
+    pub scheduled_changes_count: u64,
+    pub next_scheduled_change_at: Option<u64>,
+    /// Actions staged in the admin timelock and not executed or cancelled yet.
+    pub staged_actions_count: u64,
+    pub next_staged_unlock_at: Option<u64>,
+    pub stuck_submissions_count: u64,
+    pub total_supply: Balance,
+    pub accounts_counter: u64,
+    pub last_eth_block_height: u64,
+    /// Invariant: the contract balance covers its storage staking.
+    pub storage_covered: bool,
+    /// Invariant: tokens minted minus tokens burned add up to the total supply.
+    pub supply_consistent: bool,
+    /// Hash of the active configuration, as found in deposit and withdraw events.
+    pub config_hash: String,
 }
 
-pub enum PromiseResult {
-    NotReady,
-    Successful(Vec<u8>),
-    Failed,
+pub struct SetMaintenanceModeCallArgs {
+    pub banner: Option<String>,
 }
 
-#[derive(BorshSerialize, BorshDeserialize)]
-pub struct FunctionCallArgs {
-    pub contract: RawAddress,
-    pub input: Vec<u8>,
+pub struct GetAccountsCallArgs {
+    pub from_index: u64,
+    pub limit: u64,
 }
 
-pub struct FtResolveTransferResult {
-    pub amount: Balance,
-    pub refund_amount: Balance,
+pub struct SetRelayerFeeShareCallArgs {
+    pub relayer_fee_share_bps: u16,
 }
 
-#[derive(BorshSerialize, BorshDeserialize)]
-pub struct ViewCallArgs {
-    pub sender: RawAddress,
-    pub address: RawAddress,
-    pub amount: RawU256,
-    pub input: Vec<u8>,
+pub struct SetDepositFeeCallArgs {
+    pub deposit_fee_bps: u16,
+    /// Schedule the change to take effect at this block timestamp instead of now.
+    pub effective_at: Option<u64>,
 }
 
-#[derive(BorshSerialize, BorshDeserialize)]
-pub struct GetStorageAtArgs {
-    pub address: RawAddress,
-    pub key: RawH256,
+pub struct SetWithdrawFeeCallArgs {
+    pub withdraw_fee_bps: u16,
+    pub min_withdraw_amount: Balance,
+    /// Schedule the change to take effect at this block timestamp instead of now.
+    pub effective_at: Option<u64>,
 }
 
-pub enum KeyPrefix {
-    Code = 0x0,
-    Balance = 0x1,
-    Nonce = 0x2,
-    Storage = 0x3,
+pub struct ClaimFeesCallArgs {
+    pub recipient_id: AccountId,
 }
 
-pub fn address_to_key(prefix: KeyPrefix, address: &H160) -> [u8; 21] {
-    let mut result = [0u8; 21];
-    result[0] = prefix as u8;
-    result[1..].copy_from_slice(&address.0);
-    result
+#[derive(BorshSerialize)]
+pub struct FeeConfig {
+    pub deposit_fee_bps: u16,
+    pub relayer_fee_share_bps: u16,
+    pub withdraw_fee_bps: u16,
+    pub min_withdraw_amount: Balance,
+    pub accrued_fees: Balance,
 }
 
-pub fn storage_to_key(address: &H160, key: &H256) -> [u8; 53] {
-    let mut result = [0u8; 53];
-    result[0] = KeyPrefix::Storage as u8;
-    result[1..21].copy_from_slice(&address.0);
-    result[21..].copy_from_slice(&key.0);
-    result
+/// Limits returned by `get_effective_limits`.
+#[derive(BorshSerialize)]
+pub struct EffectiveLimits {
+    pub balance: Balance,
+    pub deposit_paused: bool,
+    pub withdraw_paused: bool,
+    pub min_withdraw_amount: Balance,
+    /// Largest amount the account can withdraw now, zero when it can't withdraw at all.
+    pub max_withdraw_amount: Balance,
+    pub withdraw_fee_bps: u16,
 }
 
-pub fn u256_to_arr(value: &U256) -> [u8; 32] {
-    let mut result = [0u8; 32];
-    value.to_big_endian(&mut result);
-    result
+pub struct ScheduleRelayerFeeShareCallArgs {
+    pub relayer_fee_share_bps: u16,
+    pub effective_at: u64,
 }
 
-pub fn log_to_bytes(log: Log) -> Vec<u8> {
-    let mut result = vec![0u8; 1 + log.topics.len() * 32 + log.data.len()];
-    result[0] = log.topics.len() as u8;
-    let mut index = 1;
-    for topic in log.topics.iter() {
-        result[index..index + 32].copy_from_slice(&topic.0);
-        index += 32;
-    }
-    result[index..].copy_from_slice(&log.data);
-    result
+#[derive(BorshSerialize, BorshDeserialize, Clone)]
+pub struct ScheduleEventTopicCallArgs {
+    pub topic: RawH256,
+    pub effective_at: u64,
 }
 
-const HEX_ALPHABET: &[u8; 16] = b"0123456789abcdef";
+pub struct PauseEthConnectorCallArgs {
+    pub paused_mask: PausedMask,
+}
 
-pub fn bytes_to_hex(v: &[u8]) -> String {
-    let mut result = String::new();
-    for x in v {
-        result.push(HEX_ALPHABET[(x / 16) as usize] as char);
-        result.push(HEX_ALPHABET[(x % 16) as usize] as char);
-    }
-    result
+pub struct SetShadowProverCallArgs {
+    pub prover_account: Option<AccountId>,
 }
 
-#[cfg(not(feature = "contract"))]
-#[inline]
-pub fn keccak(data: &[u8]) -> H256 {
-    H256::from_slice(Keccak256::digest(data).as_slice())
+/// Deposits are only minted once the light client has `min_block_confirmations`
+/// blocks on top of the proof block.
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct BlockConfirmations {
+    pub light_client_account: AccountId,
+    pub min_block_confirmations: u64,
 }
 
-pub fn near_account_to_evm_address(addr: &[u8]) -> H160 {
-    H160::from_slice(&keccak(addr)[12..])
+pub struct SetBlockConfirmationsCallArgs {
+    pub light_client_account: Option<AccountId>,
+    pub min_block_confirmations: u64,
 }
 
-impl From<json::JsonValue> for BalanceOfCallArgs {
-    fn from(v: json::JsonValue) -> Self {
-        Self {
+pub struct SetProverAccountCallArgs {
+    pub prover_account: AccountId,
+}
+
+/// Used proof recorded at some index, in recording order.
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct UsedEventIndexEntry {
+    pub proof_key: String,
+    pub eth_block_height: u64,
+}
+
+/// Ethereum blocks a proof must be behind the highest accepted block before it can be
+/// pruned, about 28 days, leaving time to relay deposits of older blocks.
+pub const DEFAULT_PRUNE_HORIZON_BLOCKS: u64 = 201_600;
+/// Lowest configurable pruning horizon, about one day of Ethereum blocks.
+pub const MIN_PRUNE_HORIZON_BLOCKS: u64 = 7_200;
+
+/// Progress of `prune_used_events` through the used proof index.
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct UsedEventPruning {
+    /// Index of the next recorded proof.
+    pub next_index: u64,
+    /// Index of the oldest record not pruned yet.
+    pub pruned_index: u64,
+    /// Proofs from blocks below this height are rejected.
+    pub pruned_before_height: u64,
+    /// Finality horizon, see `DEFAULT_PRUNE_HORIZON_BLOCKS`.
+    pub horizon_blocks: u64,
+}
+
+impl Default for UsedEventPruning {
+    fn default() -> Self {
+        Self {
+            next_index: 0,
+            pruned_index: 0,
+            pruned_before_height: 0,
+            horizon_blocks: DEFAULT_PRUNE_HORIZON_BLOCKS,
+        }
+    }
+}
+
+pub struct SetPruneHorizonCallArgs {
+    pub horizon_blocks: u64,
+}
+
+pub struct PruneUsedEventsCallArgs {
+    pub before_height: u64,
+    pub limit: u64,
+    pub refund_account_id: Option<AccountId>,
+}
+
+/// Stored for each used proof. Records written before provers were tracked hold
+/// only the submitter and read back with no `prover_account`.
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct UsedEventRecord {
+    pub submitter_id: Option<AccountId>,
+    pub prover_account: Option<AccountId>,
+}
+
+pub struct FreezeCallArgs {
+    pub frozen_mask: FrozenMask,
+}
+
+pub struct SetRefusalPolicyCallArgs {
+    pub refusal_mask: RefusalMask,
+}
+
+pub struct SetTimelockDelayCallArgs {
+    pub delay: u64,
+}
+
+pub struct StageActionCallArgs {
+    pub action: AdminAction,
+}
+
+pub struct ActionIdCallArgs {
+    pub id: u64,
+}
+
+/// State imported from another connector deployment.
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct MigrationInputData {
+    pub accounts: Vec<(AccountId, Balance)>,
+    pub total_supply: Option<Balance>,
+    pub used_proofs: Vec<String>,
+    /// Alias registry entries, as `(alias, account_id)`.
+    pub aliases: Vec<(String, AccountId)>,
+    pub last_eth_block_height: Option<u64>,
+}
+
+impl MigrationInputData {
+    /// Parse either a JSON object, so migrations can be run from near-cli and wallets,
+    /// or the Borsh encoding.
+    pub fn from_input(input: &[u8]) -> Self {
+        match parse_json(input) {
+            Some(v @ json::JsonValue::Object(_)) => Self::from(v),
+            _ => Self::try_from_slice(input).expect(FAILED_PARSE),
+        }
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize)]
+pub enum MigrationCheckResult {
+    Success,
+    AccountNotExist(Vec<AccountId>),
+    AccountAmount(Vec<(AccountId, Balance)>),
+    TotalSupply(Balance),
+    Proof(Vec<String>),
+    Alias(Vec<String>),
+    LastEthBlockHeight(u64),
+}
+
+pub struct FilterProofKeysCallArgs {
+    pub keys: Vec<String>,
+}
+
+pub struct BackfillHoldersCallArgs {
+    pub account_ids: Vec<AccountId>,
+}
+
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct RegistryConfig {
+    pub account_id: AccountId,
+    /// Chain id of the Ethereum network the custodian is deployed on.
+    pub eth_chain_id: u64,
+    pub last_registered_at: u64,
+}
+
+pub struct SetRegistryCallArgs {
+    pub account_id: Option<AccountId>,
+    pub eth_chain_id: u64,
+}
+
+pub struct SetEngineAccountCallArgs {
+    pub engine_account: Option<AccountId>,
+}
+
+/// Arguments of the `ft_on_deposit` hook called on the engine account.
+#[derive(BorshSerialize)]
+pub struct FtOnDepositArgs {
+    pub recipient: String,
+    pub amount: Balance,
+}
+
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct FinishDepositTransferCallArgs {
+    pub trace_id: String,
+    pub receiver_id: AccountId,
+    pub amount: Balance,
+}
+
+#[derive(BorshSerialize)]
+pub struct FtOnTransfer {
+    pub amount: Balance,
+    pub msg: String,
+    pub receiver_id: AccountId,
+}
+
+#[derive(BorshSerialize)]
+pub struct FtResolveTransfer {
+    pub receiver_id: AccountId,
+    pub amount: Balance,
+    pub current_account_id: AccountId,
+}
+
+#[derive(Clone)]
+pub enum PromiseResult {
+    NotReady,
+    Successful(Vec<u8>),
+    Failed,
+}
+
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct FunctionCallArgs {
+    pub contract: RawAddress,
+    pub input: Vec<u8>,
+}
+
+pub struct FtResolveTransferResult {
+    pub amount: Balance,
+    pub refund_amount: Balance,
+}
+
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct ViewCallArgs {
+    pub sender: RawAddress,
+    pub address: RawAddress,
+    pub amount: RawU256,
+    pub input: Vec<u8>,
+}
+
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct GetStorageAtArgs {
+    pub address: RawAddress,
+    pub key: RawH256,
+}
+
+pub enum KeyPrefix {
+    Code = 0x0,
+    Balance = 0x1,
+    Nonce = 0x2,
+    Storage = 0x3,
+}
+
+pub fn address_to_key(prefix: KeyPrefix, address: &H160) -> [u8; 21] {
+    let mut result = [0u8; 21];
+    result[0] = prefix as u8;
+    result[1..].copy_from_slice(&address.0);
+    result
+}
+
+pub fn storage_to_key(address: &H160, key: &H256) -> [u8; 53] {
+    let mut result = [0u8; 53];
+    result[0] = KeyPrefix::Storage as u8;
+    result[1..21].copy_from_slice(&address.0);
+    result[21..].copy_from_slice(&key.0);
+    result
+}
+
+pub fn u256_to_arr(value: &U256) -> [u8; 32] {
+    let mut result = [0u8; 32];
+    value.to_big_endian(&mut result);
+    result
+}
+
+pub fn log_to_bytes(log: Log) -> Vec<u8> {
+    let mut result = vec![0u8; 1 + log.topics.len() * 32 + log.data.len()];
+    result[0] = log.topics.len() as u8;
+    let mut index = 1;
+    for topic in log.topics.iter() {
+        result[index..index + 32].copy_from_slice(&topic.0);
+        index += 32;
+    }
+    result[index..].copy_from_slice(&log.data);
+    result
+}
+
+const HEX_ALPHABET: &[u8; 16] = b"0123456789abcdef";
+
+pub fn bytes_to_hex(v: &[u8]) -> String {
+    let mut result = String::new();
+    for x in v {
+        result.push(HEX_ALPHABET[(x / 16) as usize] as char);
+        result.push(HEX_ALPHABET[(x % 16) as usize] as char);
+    }
+    result
+}
+
+#[cfg(not(feature = "contract"))]
+#[inline]
+pub fn keccak(data: &[u8]) -> H256 {
+    H256::from_slice(Keccak256::digest(data).as_slice())
+}
+
+pub fn near_account_to_evm_address(addr: &[u8]) -> H160 {
+    H160::from_slice(&keccak(addr)[12..])
+}
+
+/// Parse a hex encoded 32-byte withdrawal memo.
+pub fn parse_memo(memo: String) -> RawH256 {
+    let data = hex::decode(memo).expect("Memo should be a valid hex string.");
+    assert_eq!(data.len(), 32, "Memo should be 32 bytes long");
+    let mut result = [0u8; 32];
+    result.copy_from_slice(&data);
+    result
+}
+
+/// Parse a hex encoded 32-byte Ethereum transaction hash, with or without `0x` prefix.
+pub fn parse_eth_tx_hash(hash: String) -> RawH256 {
+    let data = hex::decode(hash.trim_start_matches("0x"))
+        .expect("Transaction hash should be a valid hex string.");
+    assert_eq!(data.len(), 32, "Transaction hash should be 32 bytes long");
+    let mut result = [0u8; 32];
+    result.copy_from_slice(&data);
+    result
+}
+
+impl From<json::JsonValue> for SetCodeHashExpectationCallArgs {
+    fn from(v: json::JsonValue) -> Self {
+        let data = hex::decode(v.string("code_hash").expect(FAILED_PARSE))
+            .expect("Code hash should be a valid hex string.");
+        assert_eq!(data.len(), 32, "Code hash should be 32 bytes long");
+        let mut code_hash = [0u8; 32];
+        code_hash.copy_from_slice(&data);
+        Self { code_hash }
+    }
+}
+
+impl From<json::JsonValue> for RegisterWebhookCallArgs {
+    fn from(v: json::JsonValue) -> Self {
+        let data = hex::decode(v.string("url_hash").expect(FAILED_PARSE))
+            .expect("URL hash should be a valid hex string.");
+        assert_eq!(data.len(), 32, "URL hash should be 32 bytes long");
+        let mut url_hash = [0u8; 32];
+        url_hash.copy_from_slice(&data);
+        Self {
+            url_hash,
+            events: v
+                .array("events", json::JsonValue::parse_string)
+                .unwrap_or_default(),
+            account_id: v.string("account_id").ok(),
+        }
+    }
+}
+
+impl From<json::JsonValue> for UpdateWebhookCallArgs {
+    fn from(v: json::JsonValue) -> Self {
+        Self {
+            id: v.u64("id").expect(FAILED_PARSE),
+            webhook: RegisterWebhookCallArgs::from(v),
+        }
+    }
+}
+
+impl From<json::JsonValue> for WebhookIdCallArgs {
+    fn from(v: json::JsonValue) -> Self {
+        Self {
+            id: v.u64("id").expect(FAILED_PARSE),
+        }
+    }
+}
+
+impl From<json::JsonValue> for GetWebhooksCallArgs {
+    fn from(v: json::JsonValue) -> Self {
+        Self {
+            from_index: v.u64("from_index").unwrap_or(0),
+            limit: v.u64("limit").expect(FAILED_PARSE),
+        }
+    }
+}
+
+impl From<json::JsonValue> for ProofKeyCallArgs {
+    fn from(v: json::JsonValue) -> Self {
+        Self {
+            proof_key: v.string("proof_key").expect(FAILED_PARSE),
+        }
+    }
+}
+
+impl From<json::JsonValue> for RevokeProofCallArgs {
+    fn from(v: json::JsonValue) -> Self {
+        Self {
+            proof_key: v.string("proof_key").expect(FAILED_PARSE),
+            evidence: v.string("evidence").expect(FAILED_PARSE),
+            clawback_account_id: v.string("clawback_account_id").ok(),
+            clawback_amount: v.u128("clawback_amount").unwrap_or(0),
+        }
+    }
+}
+
+impl From<json::JsonValue> for FindDepositCallArgs {
+    fn from(v: json::JsonValue) -> Self {
+        match v.string("trace_id") {
+            Ok(trace_id) => Self::TraceId(trace_id),
+            Err(_) => Self::ProofKey(v.string("proof_key").expect(FAILED_PARSE)),
+        }
+    }
+}
+
+impl From<json::JsonValue> for BalanceOfCallArgs {
+    fn from(v: json::JsonValue) -> Self {
+        Self {
             account_id: v.string("account_id").expect(FAILED_PARSE),
         }
     }
@@ -221,6 +1229,74 @@ impl From<json::JsonValue> for InitCallArgs {
         Self {
             eth_custodian_address: v.string("eth_custodian_address").expect(FAILED_PARSE),
             prover_account: v.string("prover_account").expect(FAILED_PARSE),
+            owner_id: v.string("owner_id").ok(),
+            relayer_fee_share_bps: v.u16("relayer_fee_share_bps").ok(),
+            deposit_fee_bps: v.u16("deposit_fee_bps").ok(),
+            withdraw_fee_bps: v.u16("withdraw_fee_bps").ok(),
+            min_withdraw_amount: v.u128("min_withdraw_amount").ok(),
+        }
+    }
+}
+
+impl From<json::JsonValue> for OwnerCallArgs {
+    fn from(v: json::JsonValue) -> Self {
+        Self {
+            owner_id: v.string("owner_id").expect(FAILED_PARSE),
+        }
+    }
+}
+
+impl From<json::JsonValue> for WithdrawAllowanceCallArgs {
+    fn from(v: json::JsonValue) -> Self {
+        Self {
+            executor_id: v.string("executor_id").expect(FAILED_PARSE),
+            amount: v.u128("amount").expect(FAILED_PARSE),
+        }
+    }
+}
+
+impl From<json::JsonValue> for AuthorizeWithdrawIntentCallArgs {
+    fn from(v: json::JsonValue) -> Self {
+        Self {
+            executor_id: v.string("executor_id").ok(),
+            recipient_id: v.string("recipient_id").expect(FAILED_PARSE),
+            max_amount: v.u128("max_amount").expect(FAILED_PARSE),
+            expires_at: v.u64("expires_at").expect(FAILED_PARSE),
+        }
+    }
+}
+
+impl From<json::JsonValue> for ExecuteWithdrawIntentCallArgs {
+    fn from(v: json::JsonValue) -> Self {
+        Self {
+            id: v.u64("id").expect(FAILED_PARSE),
+            amount: v.u128("amount").expect(FAILED_PARSE),
+            memo: v.string("memo").ok().map(parse_memo),
+        }
+    }
+}
+
+impl From<json::JsonValue> for WithdrawIntentIdCallArgs {
+    fn from(v: json::JsonValue) -> Self {
+        Self {
+            id: v.u64("id").expect(FAILED_PARSE),
+        }
+    }
+}
+
+impl From<json::JsonValue> for WithdrawExecutorCallArgs {
+    fn from(v: json::JsonValue) -> Self {
+        Self {
+            executor_id: v.string("executor_id").expect(FAILED_PARSE),
+        }
+    }
+}
+
+impl From<json::JsonValue> for GetWithdrawAllowanceCallArgs {
+    fn from(v: json::JsonValue) -> Self {
+        Self {
+            owner_id: v.string("owner_id").expect(FAILED_PARSE),
+            executor_id: v.string("executor_id").expect(FAILED_PARSE),
         }
     }
 }
@@ -230,6 +1306,28 @@ impl From<json::JsonValue> for WithdrawCallArgs {
         Self {
             recipient_id: v.string("recipient_id").expect(FAILED_PARSE),
             amount: v.u128("amount").expect(FAILED_PARSE),
+            memo: v.string("memo").ok().map(parse_memo),
+        }
+    }
+}
+
+impl From<json::JsonValue> for SetEthRecipientBlockedCallArgs {
+    fn from(v: json::JsonValue) -> Self {
+        let eth_address = v.string("eth_address").expect(FAILED_PARSE);
+        Self {
+            eth_address: EthAddress::try_from(eth_address.as_str())
+                .unwrap_or_else(|err| sdk::panic_utf8(err.as_ref())),
+            blocked: v.bool("blocked").expect(FAILED_PARSE),
+        }
+    }
+}
+
+impl From<json::JsonValue> for EthRecipientCallArgs {
+    fn from(v: json::JsonValue) -> Self {
+        let eth_address = v.string("eth_address").expect(FAILED_PARSE);
+        Self {
+            eth_address: EthAddress::try_from(eth_address.as_str())
+                .unwrap_or_else(|err| sdk::panic_utf8(err.as_ref())),
         }
     }
 }
@@ -259,6 +1357,317 @@ impl From<json::JsonValue> for StorageDepositCallArgs {
     }
 }
 
+impl From<json::JsonValue> for AliasCallArgs {
+    fn from(v: json::JsonValue) -> Self {
+        Self {
+            alias: v.string("alias").expect(FAILED_PARSE),
+        }
+    }
+}
+
+impl From<json::JsonValue> for BackfillHoldersCallArgs {
+    fn from(v: json::JsonValue) -> Self {
+        Self {
+            account_ids: v
+                .array("account_ids", json::JsonValue::parse_string)
+                .expect(FAILED_PARSE),
+        }
+    }
+}
+
+impl From<json::JsonValue> for FilterProofKeysCallArgs {
+    fn from(v: json::JsonValue) -> Self {
+        Self {
+            keys: v
+                .array("keys", json::JsonValue::parse_string)
+                .expect(FAILED_PARSE),
+        }
+    }
+}
+
+impl From<json::JsonValue> for SetRegistryCallArgs {
+    fn from(v: json::JsonValue) -> Self {
+        let account_id = v.string("account_id").ok();
+        let eth_chain_id = match account_id {
+            Some(_) => v.u64("eth_chain_id").expect(FAILED_PARSE),
+            None => 0,
+        };
+        Self {
+            account_id,
+            eth_chain_id,
+        }
+    }
+}
+
+impl From<json::JsonValue> for SetEngineAccountCallArgs {
+    fn from(v: json::JsonValue) -> Self {
+        Self {
+            engine_account: v.string("engine_account").ok(),
+        }
+    }
+}
+
+impl From<json::JsonValue> for GetAccountHistoryCallArgs {
+    fn from(v: json::JsonValue) -> Self {
+        Self {
+            account_id: v.string("account_id").expect(FAILED_PARSE),
+            from_index: v.u64("from_index").unwrap_or(0),
+            limit: v.u64("limit").expect(FAILED_PARSE),
+        }
+    }
+}
+
+impl From<json::JsonValue> for GetAccountsCallArgs {
+    fn from(v: json::JsonValue) -> Self {
+        Self {
+            from_index: v.u64("from_index").unwrap_or(0),
+            limit: v.u64("limit").expect(FAILED_PARSE),
+        }
+    }
+}
+
+impl From<json::JsonValue> for SetRelayerFeeShareCallArgs {
+    fn from(v: json::JsonValue) -> Self {
+        Self {
+            relayer_fee_share_bps: v.u16("relayer_fee_share_bps").expect(FAILED_PARSE),
+        }
+    }
+}
+
+impl From<json::JsonValue> for SetDepositFeeCallArgs {
+    fn from(v: json::JsonValue) -> Self {
+        Self {
+            deposit_fee_bps: v.u16("deposit_fee_bps").expect(FAILED_PARSE),
+            effective_at: v.u64("effective_at").ok(),
+        }
+    }
+}
+
+impl From<json::JsonValue> for SetWithdrawFeeCallArgs {
+    fn from(v: json::JsonValue) -> Self {
+        Self {
+            withdraw_fee_bps: v.u16("withdraw_fee_bps").expect(FAILED_PARSE),
+            min_withdraw_amount: v.u128("min_withdraw_amount").expect(FAILED_PARSE),
+            effective_at: v.u64("effective_at").ok(),
+        }
+    }
+}
+
+impl From<json::JsonValue> for ClaimFeesCallArgs {
+    fn from(v: json::JsonValue) -> Self {
+        Self {
+            recipient_id: v.string("recipient_id").expect(FAILED_PARSE),
+        }
+    }
+}
+
+impl From<json::JsonValue> for ScheduleRelayerFeeShareCallArgs {
+    fn from(v: json::JsonValue) -> Self {
+        Self {
+            relayer_fee_share_bps: v.u16("relayer_fee_share_bps").expect(FAILED_PARSE),
+            effective_at: v.u64("effective_at").expect(FAILED_PARSE),
+        }
+    }
+}
+
+impl From<json::JsonValue> for ScheduleEventTopicCallArgs {
+    fn from(v: json::JsonValue) -> Self {
+        let data = hex::decode(v.string("topic").expect(FAILED_PARSE))
+            .expect("Event topic should be a valid hex string.");
+        assert_eq!(data.len(), 32, "Event topic should be 32 bytes long");
+        let mut topic = [0u8; 32];
+        topic.copy_from_slice(&data);
+        Self {
+            topic,
+            effective_at: v.u64("effective_at").expect(FAILED_PARSE),
+        }
+    }
+}
+
+impl From<json::JsonValue> for SetMaintenanceModeCallArgs {
+    fn from(v: json::JsonValue) -> Self {
+        Self {
+            banner: v.string("banner").ok(),
+        }
+    }
+}
+
+impl From<json::JsonValue> for PauseEthConnectorCallArgs {
+    fn from(v: json::JsonValue) -> Self {
+        Self {
+            paused_mask: v.u8("paused_mask").expect(FAILED_PARSE),
+        }
+    }
+}
+
+impl From<json::JsonValue> for SetShadowProverCallArgs {
+    fn from(v: json::JsonValue) -> Self {
+        Self {
+            prover_account: v.string("prover_account").ok(),
+        }
+    }
+}
+
+impl From<json::JsonValue> for SetPruneHorizonCallArgs {
+    fn from(v: json::JsonValue) -> Self {
+        Self {
+            horizon_blocks: v.u64("horizon_blocks").expect(FAILED_PARSE),
+        }
+    }
+}
+
+impl From<json::JsonValue> for PruneUsedEventsCallArgs {
+    fn from(v: json::JsonValue) -> Self {
+        Self {
+            before_height: v.u64("before_height").expect(FAILED_PARSE),
+            limit: v.u64("limit").expect(FAILED_PARSE),
+            refund_account_id: v.string("refund_account_id").ok(),
+        }
+    }
+}
+
+impl From<json::JsonValue> for SetBlockConfirmationsCallArgs {
+    fn from(v: json::JsonValue) -> Self {
+        Self {
+            light_client_account: v.string("light_client_account").ok(),
+            min_block_confirmations: v.u64("min_block_confirmations").unwrap_or(0),
+        }
+    }
+}
+
+impl From<json::JsonValue> for SetProverAccountCallArgs {
+    fn from(v: json::JsonValue) -> Self {
+        Self {
+            prover_account: v.string("prover_account").expect(FAILED_PARSE),
+        }
+    }
+}
+
+/// Balances are decimal strings, as they don't fit in a JSON number.
+impl From<json::JsonValue> for MigrationInputData {
+    fn from(v: json::JsonValue) -> Self {
+        let parse_balance = |s: String| s.parse::<Balance>().expect(FAILED_PARSE);
+        Self {
+            accounts: v
+                .array("accounts", |item| {
+                    (
+                        item.string("account_id").expect(FAILED_PARSE),
+                        parse_balance(item.string("balance").expect(FAILED_PARSE)),
+                    )
+                })
+                .unwrap_or_default(),
+            total_supply: v.string("total_supply").ok().map(parse_balance),
+            used_proofs: v
+                .array("used_proofs", json::JsonValue::parse_string)
+                .unwrap_or_default(),
+            aliases: v
+                .array("aliases", |item| {
+                    (
+                        item.string("alias").expect(FAILED_PARSE),
+                        item.string("account_id").expect(FAILED_PARSE),
+                    )
+                })
+                .unwrap_or_default(),
+            last_eth_block_height: v.u64("last_eth_block_height").ok(),
+        }
+    }
+}
+
+impl From<json::JsonValue> for FreezeCallArgs {
+    fn from(v: json::JsonValue) -> Self {
+        Self {
+            frozen_mask: v.u8("frozen_mask").expect(FAILED_PARSE),
+        }
+    }
+}
+
+impl From<json::JsonValue> for SetRefusalPolicyCallArgs {
+    fn from(v: json::JsonValue) -> Self {
+        Self {
+            refusal_mask: v.u8("refusal_mask").expect(FAILED_PARSE),
+        }
+    }
+}
+
+impl From<json::JsonValue> for SetTimelockDelayCallArgs {
+    fn from(v: json::JsonValue) -> Self {
+        Self {
+            delay: v.u64("delay").expect(FAILED_PARSE),
+        }
+    }
+}
+
+impl From<json::JsonValue> for SetEthCustodianAddressCallArgs {
+    fn from(v: json::JsonValue) -> Self {
+        let eth_address = v.string("eth_custodian_address").expect(FAILED_PARSE);
+        Self {
+            eth_custodian_address: EthAddress::try_from(eth_address.as_str())
+                .unwrap_or_else(|err| sdk::panic_utf8(err.as_ref())),
+            grace_blocks: v.u64("grace_blocks").ok(),
+            grace_until_eth_block: v.u64("grace_until_eth_block").ok(),
+        }
+    }
+}
+
+impl From<json::JsonValue> for StageActionCallArgs {
+    /// The `action` name selects the variant, its arguments are those of the matching
+    /// setter, e.g. `{"action":"set_deposit_fee","deposit_fee_bps":30}`.
+    fn from(v: json::JsonValue) -> Self {
+        let action = match v.string("action").expect(FAILED_PARSE).as_str() {
+            "set_eth_custodian_address" => {
+                AdminAction::SetEthCustodianAddress(SetEthCustodianAddressCallArgs::from(v))
+            }
+            "set_prover_account" => {
+                AdminAction::SetProverAccount(v.string("prover_account").expect(FAILED_PARSE))
+            }
+            "set_relayer_fee_share" => {
+                AdminAction::SetRelayerFeeShare(v.u16("relayer_fee_share_bps").expect(FAILED_PARSE))
+            }
+            "set_deposit_fee" => {
+                AdminAction::SetDepositFee(v.u16("deposit_fee_bps").expect(FAILED_PARSE))
+            }
+            "set_withdraw_fee" => AdminAction::SetWithdrawFee {
+                withdraw_fee_bps: v.u16("withdraw_fee_bps").expect(FAILED_PARSE),
+                min_withdraw_amount: v.u128("min_withdraw_amount").expect(FAILED_PARSE),
+            },
+            "upgrade" => AdminAction::Upgrade(SetCodeHashExpectationCallArgs::from(v).code_hash),
+            "set_timelock_delay" => {
+                AdminAction::SetTimelockDelay(v.u64("delay").expect(FAILED_PARSE))
+            }
+            "set_deposited_event_layout" => {
+                AdminAction::SetDepositedEventLayout(EventLayout::from(v))
+            }
+            "set_engine_account" => {
+                AdminAction::SetEngineAccount(SetEngineAccountCallArgs::from(v).engine_account)
+            }
+            "schedule_deposited_event_topic" => {
+                AdminAction::ScheduleDepositedEventTopic(ScheduleEventTopicCallArgs::from(v))
+            }
+            "revoke_proof" => AdminAction::RevokeProof(RevokeProofCallArgs::from(v)),
+            _ => sdk::panic_utf8(FAILED_PARSE.as_bytes()),
+        };
+        Self { action }
+    }
+}
+
+impl From<json::JsonValue> for ActionIdCallArgs {
+    fn from(v: json::JsonValue) -> Self {
+        Self {
+            id: v.u64("id").expect(FAILED_PARSE),
+        }
+    }
+}
+
+impl From<json::JsonValue> for EventLayout {
+    fn from(v: json::JsonValue) -> Self {
+        Self {
+            indexed: v
+                .array("indexed", json::JsonValue::parse_bool)
+                .expect(FAILED_PARSE),
+        }
+    }
+}
+
 impl From<json::JsonValue> for TransferCallCallArgs {
     fn from(v: json::JsonValue) -> Self {
         Self {
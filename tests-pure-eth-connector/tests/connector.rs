@@ -19,13 +19,110 @@ impl EthConnector {
         header_data: Vec<u8>,
         proof: Vec<Vec<u8>>,
         skip_bridge_call: bool,
+        eth_tx_hash: Option<String>,
+    ) {
+    }
+
+    pub fn deposit_if_unused(
+        &self,
+        log_index: u64,
+        log_entry_data: Vec<u8>,
+        receipt_index: u64,
+        receipt_data: Vec<u8>,
+        header_data: Vec<u8>,
+        proof: Vec<Vec<u8>>,
+        skip_bridge_call: bool,
+    ) {
+    }
+
+    pub fn preflight(
+        &self,
+        log_index: u64,
+        log_entry_data: Vec<u8>,
+        receipt_index: u64,
+        receipt_data: Vec<u8>,
+        header_data: Vec<u8>,
+        proof: Vec<Vec<u8>>,
+        skip_bridge_call: bool,
+    ) {
+    }
+
+    pub fn mock_deposit(
+        &self,
+        log_index: u64,
+        log_entry_data: Vec<u8>,
+        receipt_index: u64,
+        receipt_data: Vec<u8>,
+        header_data: Vec<u8>,
+        proof: Vec<Vec<u8>>,
+        skip_bridge_call: bool,
+        verified: Option<bool>,
     ) {
     }
 
     pub fn withdraw(&self, recipient_id: AccountId, amount: u64) {}
 
+    pub fn withdraw_for(&self, owner_id: AccountId, recipient_id: AccountId, amount: u64) {}
+
+    pub fn set_eth_recipient_blocked(&self, eth_address: String, blocked: bool) {}
+
+    pub fn is_eth_recipient_blocked(&self, eth_address: String) {}
+
+    pub fn approve_withdraw_executor(&self, executor_id: AccountId, amount: u64) {}
+
+    pub fn revoke_withdraw_executor(&self, executor_id: AccountId) {}
+
+    pub fn authorize_withdraw_intent(
+        &self,
+        executor_id: Option<AccountId>,
+        recipient_id: AccountId,
+        max_amount: u64,
+        expires_at: u64,
+    ) {
+    }
+
+    pub fn execute_withdraw_intent(&self, id: u64, amount: u64) {}
+
+    pub fn get_withdraw_intent(&self, id: u64) {}
+
+    pub fn register_webhook(
+        &self,
+        url_hash: String,
+        events: Vec<String>,
+        account_id: Option<AccountId>,
+    ) {
+    }
+
+    pub fn remove_webhook(&self, id: u64) {}
+
+    pub fn get_webhook(&self, id: u64) {}
+
+    pub fn get_webhooks(&self, from_index: u64, limit: u64) {}
+
+    pub fn get_withdraw_allowance(&self, owner_id: AccountId, executor_id: AccountId) {}
+
     pub fn ft_total_supply(&self) {}
 
+    pub fn ft_metadata(&self) {}
+
+    pub fn reconcile(&self) {}
+
+    pub fn get_operator_tasks(&self) {}
+
+    pub fn get_deposit_storage_cost(&self) {}
+
+    pub fn get_gas_budget(&self) {}
+
+    pub fn set_deposit_fee(&self, deposit_fee_bps: u16) {}
+
+    pub fn set_withdraw_fee(&self, withdraw_fee_bps: u16, min_withdraw_amount: u64) {}
+
+    pub fn get_effective_limits(&self, account_id: AccountId) {}
+
+    pub fn claim_fees(&self, recipient_id: AccountId) {}
+
+    pub fn get_fee_config(&self) {}
+
     pub fn ft_transfer(&self, receiver_id: AccountId, amount: u64, memo: Option<String>) {}
 
     pub fn ft_transfer_call(
@@ -42,4 +139,136 @@ impl EthConnector {
     pub fn storage_withdraw(&self, amount: Option<u64>) {}
 
     pub fn storage_balance_of(&self, account_id: AccountId) {}
+
+    pub fn propose_owner(&self, owner_id: AccountId) {}
+
+    pub fn accept_owner(&self) {}
+
+    pub fn get_owner_id(&self) {}
+
+    pub fn set_registry(&self, account_id: Option<AccountId>, eth_chain_id: u64) {}
+
+    pub fn register_self(&self) {}
+
+    pub fn get_registry(&self) {}
+
+    pub fn set_shadow_prover(&self, prover_account: Option<AccountId>) {}
+
+    pub fn get_shadow_prover(&self) {}
+
+    pub fn register_alias(&mut self, alias: String) {}
+
+    pub fn get_alias_account(&self, alias: String) {}
+
+    pub fn set_block_confirmations(
+        &self,
+        light_client_account: Option<AccountId>,
+        min_block_confirmations: u64,
+    ) {
+    }
+
+    pub fn irreversibly_freeze(&self, frozen_mask: u8) {}
+
+    pub fn get_frozen_flags(&self) {}
+
+    pub fn get_scheduled_changes(&self) {}
+
+    pub fn set_refusal_policy(&self, refusal_mask: u8) {}
+
+    pub fn set_timelock_delay(&self, delay: u64) {}
+
+    pub fn set_eth_custodian_address(
+        &self,
+        eth_custodian_address: String,
+        grace_blocks: Option<u64>,
+        grace_until_eth_block: Option<u64>,
+    ) {
+    }
+
+    pub fn set_prover_account(&self, prover_account: String) {}
+
+    pub fn get_schema_log(&self) {}
+
+    pub fn execute_action(&self, id: u64) {}
+
+    pub fn cancel_action(&self, id: u64) {}
+
+    pub fn get_refusal_policy(&self) {}
+
+    pub fn set_code_hash_expectation(&self, code_hash: String) {}
+
+    pub fn set_paused_flags(&self, paused_mask: u8) {}
+
+    pub fn get_paused_flags(&self) {}
+
+    pub fn get_accounts_counter(&self) {}
+
+    pub fn set_relayer_fee_share(&self, relayer_fee_share_bps: u16) {}
+
+    pub fn schedule_deposited_event_topic(&self, topic: String, effective_at: u64) {}
+
+    pub fn get_event_topics(&self) {}
+
+    pub fn set_maintenance_mode(&self, banner: Option<String>) {}
+
+    pub fn get_status(&self) {}
+
+    pub fn get_last_eth_block_height(&self) {}
+
+    pub fn find_deposit(&self, proof_key: Option<String>, trace_id: Option<String>) {}
+
+    pub fn get_proof_key(
+        &self,
+        log_index: u64,
+        log_entry_data: Vec<u8>,
+        receipt_index: u64,
+        receipt_data: Vec<u8>,
+        header_data: Vec<u8>,
+        proof: Vec<Vec<u8>>,
+        skip_bridge_call: bool,
+    ) {
+    }
+
+    pub fn is_used_proof(
+        &self,
+        log_index: u64,
+        log_entry_data: Vec<u8>,
+        receipt_index: u64,
+        receipt_data: Vec<u8>,
+        header_data: Vec<u8>,
+        proof: Vec<Vec<u8>>,
+        skip_bridge_call: bool,
+    ) {
+    }
+
+    pub fn is_used_event(&self, proof_key: String) {}
+
+    pub fn prune_used_events(
+        &mut self,
+        before_height: u64,
+        limit: u64,
+        refund_account_id: Option<AccountId>,
+    ) {
+    }
+
+    pub fn get_proof_submitter(&self, proof_key: String) {}
+
+    pub fn get_proof_prover(&self, proof_key: String) {}
+
+    pub fn get_test_vectors(&self) {}
+
+    pub fn complete_deposit(&mut self, proof_key: String) {}
+
+    pub fn revoke_proof(
+        &self,
+        proof_key: String,
+        evidence: String,
+        clawback_account_id: Option<AccountId>,
+        clawback_amount: u64,
+    ) {
+    }
+
+    pub fn get_revoked_proof(&self, proof_key: String) {}
+
+    pub fn get_account_history(&self, account_id: AccountId, from_index: u64, limit: u64) {}
 }
@@ -2,10 +2,14 @@
 #![allow(dead_code)]
 #![allow(unused_variables)]
 use connector::EthConnectorContract;
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
 use near_sdk::serde::{Deserialize, Serialize};
 use near_sdk::serde_json;
 use near_sdk_sim::types::AccountId;
-use near_sdk_sim::{call, deploy, init_simulator, view, ContractAccount, UserAccount, DEFAULT_GAS};
+use near_sdk_sim::{
+    call, deploy, init_simulator, to_yocto, view, ContractAccount, ExecutionResult, UserAccount,
+    DEFAULT_GAS,
+};
 use std::convert::TryFrom;
 
 mod connector;
@@ -22,6 +26,21 @@ const CUSTODIAN_ADDRESS: &'static str = "b9f7219e434EAA7021Ae5f9Ecd0CaBc2405447A
 const PROVER_ACCOUNT: &'static str = "eth_connector.root";
 const CONTRACT_ACC: &'static str = "eth_connector.root";
 const RECIPIENT_ETH_ADDRESS: &'static str = "891b2749238b27ff58e951088e55b04de71dc374";
+const PROOF_BLOCK_HEIGHT: u64 = 9782738;
+const DEPOSIT_TX_HASH: &'static str =
+    "0x2a7a5ec3b5dc5d7a4b5d55f4eca7e4d4cb8cc9b1e1b7f2a5a0d0c9b5b0f3e2d1";
+/// Topic0 of `DepositedToNear(address,string,uint256,uint256)`, as found in `PROOF_DATA`.
+const DEPOSITED_EVENT_TOPIC: &'static str =
+    "5bfdafec39ae9260e2dc42fa231501f465fbaf57a6bbbcc5179d0e566933daae";
+const PAUSE_DEPOSIT: u8 = 1 << 0;
+const PAUSE_WITHDRAW: u8 = 1 << 1;
+const FREEZE_FEES: u8 = 1 << 2;
+const FREEZE_CUSTODIAN: u8 = 1 << 3;
+const FREEZE_PROVER: u8 = 1 << 4;
+const REFUSE_BLOCKLISTED: u8 = 1 << 2;
+const PRUNE_HORIZON_BLOCKS: u64 = 201_600;
+/// 432 bytes at 1e20 yoctoNEAR per byte.
+const USED_PROOF_STORAGE_DEPOSIT: u128 = 43_200_000_000_000_000_000_000;
 
 #[derive(Serialize, Deserialize)]
 #[serde(crate = "near_sdk::serde")]
@@ -35,6 +54,228 @@ pub struct Proof {
     pub skip_bridge_call: bool,
 }
 
+#[derive(BorshDeserialize)]
+pub struct DepositReceipt {
+    pub proof_key: String,
+    pub new_owner_id: AccountId,
+    pub amount: u128,
+    pub fee: u128,
+    pub relayer_id: AccountId,
+    pub relayer_fee: u128,
+    pub protocol_fee: u128,
+    pub eth_block_height: u64,
+    pub trace_id: String,
+    pub prover_gas: u64,
+    pub finish_deposit_gas: u64,
+    pub eth_tx_hash: Option<[u8; 32]>,
+}
+
+#[derive(BorshDeserialize)]
+pub struct GasBudget {
+    pub verify_log_entry: u64,
+    pub finish_deposit: u64,
+    pub ft_on_deposit: u64,
+    pub finish_engine_deposit: u64,
+    pub ft_transfer_call: u64,
+    pub ft_resolve_transfer: u64,
+}
+
+#[derive(BorshDeserialize)]
+pub struct SchemaLogEntry {
+    pub contract_version: String,
+    pub state_version: u32,
+    pub block_height: u64,
+}
+
+#[derive(BorshDeserialize)]
+pub struct TestVector {
+    pub name: String,
+    pub input: String,
+    pub output: String,
+}
+
+#[derive(BorshDeserialize, Debug, PartialEq)]
+pub struct WithdrawIntent {
+    pub owner_id: AccountId,
+    pub executor_id: Option<AccountId>,
+    pub recipient_id: AccountId,
+    pub remaining_amount: u128,
+    pub expires_at: u64,
+}
+
+#[derive(BorshDeserialize, Debug, PartialEq)]
+pub struct WebhookDescriptor {
+    pub owner_id: AccountId,
+    pub url_hash: [u8; 32],
+    pub events: Vec<String>,
+    pub account_id: Option<AccountId>,
+}
+
+#[derive(BorshSerialize)]
+pub struct FinishDepositTransferCallArgs {
+    pub trace_id: String,
+    pub receiver_id: AccountId,
+    pub amount: u128,
+}
+
+#[derive(BorshDeserialize, Debug, PartialEq)]
+pub struct GuardRefusal {
+    pub guard: u8,
+    pub error: String,
+}
+
+#[derive(BorshDeserialize, Debug, PartialEq)]
+pub struct FeeConfig {
+    pub deposit_fee_bps: u16,
+    pub relayer_fee_share_bps: u16,
+    pub withdraw_fee_bps: u16,
+    pub min_withdraw_amount: u128,
+    pub accrued_fees: u128,
+}
+
+#[derive(BorshDeserialize, Debug, PartialEq)]
+pub struct EffectiveLimits {
+    pub balance: u128,
+    pub deposit_paused: bool,
+    pub withdraw_paused: bool,
+    pub min_withdraw_amount: u128,
+    pub max_withdraw_amount: u128,
+    pub withdraw_fee_bps: u16,
+}
+
+#[derive(BorshDeserialize)]
+pub struct RegistryConfig {
+    pub account_id: AccountId,
+    pub eth_chain_id: u64,
+    pub last_registered_at: u64,
+}
+
+#[derive(BorshDeserialize, Debug, PartialEq)]
+pub struct ShadowProver {
+    pub account_id: AccountId,
+    pub agreements: u64,
+    pub disagreements: u64,
+    pub failures: u64,
+}
+
+#[derive(BorshDeserialize, Debug, PartialEq)]
+pub enum DepositStatus {
+    Deposited,
+    AlreadyUsed,
+    Retryable,
+    Deferred,
+    NotConfirmed,
+}
+
+#[derive(BorshDeserialize)]
+pub struct RevokedProof {
+    pub evidence: String,
+    pub clawback_account_id: Option<AccountId>,
+    pub clawed_back: u128,
+    pub timestamp: u64,
+}
+
+#[derive(BorshDeserialize)]
+pub struct ReconcileReport {
+    pub total_minted: u128,
+    pub total_burned: u128,
+    pub total_supply: u128,
+    pub consistent: bool,
+}
+
+#[derive(BorshDeserialize)]
+pub struct StuckSubmission {
+    pub proof_key: String,
+    pub new_owner_id: AccountId,
+    pub amount: u128,
+    pub relayer_id: AccountId,
+    pub timestamp: u64,
+    pub trace_id: String,
+}
+
+#[derive(BorshDeserialize)]
+pub struct OperatorTasks {
+    pub stuck_submissions: Vec<StuckSubmission>,
+    pub claimable_fees: u128,
+    pub supply_consistent: bool,
+    pub storage_covered: bool,
+    pub shadow_prover_disagreements: u64,
+}
+
+#[derive(BorshDeserialize)]
+pub struct ConnectorStatus {
+    pub maintenance_banner: Option<String>,
+    pub paused_mask: u8,
+    pub contract_version: String,
+    pub state_version: u32,
+    pub scheduled_changes_count: u64,
+    pub next_scheduled_change_at: Option<u64>,
+    pub staged_actions_count: u64,
+    pub next_staged_unlock_at: Option<u64>,
+    pub stuck_submissions_count: u64,
+    pub total_supply: u128,
+    pub accounts_counter: u64,
+    pub last_eth_block_height: u64,
+    pub storage_covered: bool,
+    pub supply_consistent: bool,
+    pub config_hash: String,
+}
+
+#[derive(BorshDeserialize, Debug, PartialEq)]
+pub enum ParamChange {
+    RelayerFeeShare(u16),
+    DepositedEventTopic([u8; 32]),
+    DepositFee(u16),
+    WithdrawFee(u16),
+    MinWithdrawAmount(u128),
+}
+
+#[derive(BorshDeserialize)]
+pub struct ScheduledParamChange {
+    pub effective_at: u64,
+    pub change: ParamChange,
+}
+
+#[derive(BorshDeserialize, Debug, PartialEq)]
+pub enum HistoryKind {
+    Deposit,
+    Withdraw,
+}
+
+#[derive(BorshDeserialize)]
+pub struct HistoryEntry {
+    pub kind: HistoryKind,
+    pub amount: u128,
+    pub fee: u128,
+    pub eth_address: String,
+    pub timestamp: u64,
+}
+
+#[derive(BorshDeserialize)]
+pub struct EventTopics {
+    pub deposited: [u8; 32],
+}
+
+#[derive(BorshDeserialize, Debug, PartialEq)]
+pub enum PreflightFailure {
+    Maintenance,
+    DepositPaused,
+    InvalidProof(String),
+    ProofAlreadyUsed,
+    CustodianMismatch,
+    FeeExceedsAmount,
+    ProofTooOld,
+    InvalidEvent(String),
+    UnregisteredAlias,
+    AliasRegisteredLate,
+}
+
+#[derive(BorshDeserialize)]
+pub struct PreflightReport {
+    pub failures: Vec<PreflightFailure>,
+    pub required_gas: u64,
+}
+
 fn init() -> (UserAccount, ContractAccount<EthConnectorContract>) {
     let master_account = init_simulator(None);
 
@@ -53,6 +294,19 @@ fn init_test() {
     let (_master_account, _contract_account) = init();
 }
 
+#[test]
+#[should_panic]
+fn test_init_zero_custodian_address() {
+    let master_account = init_simulator(None);
+    deploy! {
+        contract: EthConnectorContract,
+        contract_id: CONTRACT_ACC,
+        bytes: &TOKEN_WASM_BYTES,
+        signer_account: master_account,
+        init_method: new(PROVER_ACCOUNT.into(), "0000000000000000000000000000000000000000".into())
+    };
+}
+
 #[test]
 fn test_balance_of() {
     let (_master_account, contract) = init();
@@ -101,9 +355,42 @@ fn test_sim_withdraw() {
 }
 
 fn call_deposit(master_account: &UserAccount, contract: &ContractAccount<EthConnectorContract>) {
+    call_deposit_with_tx_hash(master_account, contract, None);
+}
+
+#[test]
+fn test_deposit_proof_reuse_error_code() {
+    let (master_account, contract) = init();
+    call_deposit(&master_account, &contract);
+    let res = call_deposit_with_tx_hash(&master_account, &contract, None);
+    assert_error_code(&res, "ERR_PROOF_EXIST");
+
+    let balance = view!(contract.ft_balance_of(DEPOSITED_RECIPIENT.into())).unwrap_json::<u128>();
+    assert_eq!(balance, DEPOSITED_AMOUNT - DEPOSITED_FEE);
+}
+
+fn call_deposit_with_tx_hash(
+    master_account: &UserAccount,
+    contract: &ContractAccount<EthConnectorContract>,
+    eth_tx_hash: Option<String>,
+) -> ExecutionResult {
+    call_deposit_attaching(
+        master_account,
+        contract,
+        eth_tx_hash,
+        USED_PROOF_STORAGE_DEPOSIT,
+    )
+}
+
+fn call_deposit_attaching(
+    master_account: &UserAccount,
+    contract: &ContractAccount<EthConnectorContract>,
+    eth_tx_hash: Option<String>,
+    attached_deposit: u128,
+) -> ExecutionResult {
     let proof: Proof = serde_json::from_str(PROOF_DATA).unwrap();
 
-    let _res = call!(
+    call!(
         master_account,
         contract.deposit(
             proof.log_index,
@@ -112,12 +399,160 @@ fn call_deposit(master_account: &UserAccount, contract: &ContractAccount<EthConn
             proof.receipt_data,
             proof.header_data,
             proof.proof,
-            proof.skip_bridge_call
+            proof.skip_bridge_call,
+            eth_tx_hash
+        ),
+        deposit = attached_deposit,
+        gas = DEFAULT_GAS * 3
+    )
+}
+
+/// Deposit of the test proof with its `root` recipient replaced by another 4-byte one.
+fn call_deposit_to(
+    master_account: &UserAccount,
+    contract: &ContractAccount<EthConnectorContract>,
+    recipient: &str,
+) -> ExecutionResult {
+    let mut proof: Proof = serde_json::from_str(PROOF_DATA).unwrap();
+    let pos = proof
+        .log_entry_data
+        .windows(4)
+        .position(|w| w == DEPOSITED_RECIPIENT.as_bytes())
+        .unwrap();
+    proof.log_entry_data[pos..pos + 4].copy_from_slice(recipient.as_bytes());
+    call!(
+        master_account,
+        contract.deposit(
+            proof.log_index,
+            proof.log_entry_data,
+            proof.receipt_index,
+            proof.receipt_data,
+            proof.header_data,
+            proof.proof,
+            proof.skip_bridge_call,
+            None
         ),
+        deposit = USED_PROOF_STORAGE_DEPOSIT,
         gas = DEFAULT_GAS * 3
+    )
+}
+
+/// Assert that the call or one of its promises failed with the given error code.
+fn assert_error_code(res: &ExecutionResult, code: &str) {
+    let errors = format!("{:?}", res.promise_errors());
+    assert!(errors.contains(code), "Expected {} in {}", code, errors);
+}
+
+#[test]
+fn test_set_eth_custodian_address() {
+    let (master_account, contract) = init();
+    let res = call!(
+        master_account,
+        contract.set_eth_custodian_address(RECIPIENT_ETH_ADDRESS.into(), None, None)
     );
+    assert!(!res.is_ok());
+    call!(
+        contract.user_account,
+        contract.set_eth_custodian_address(RECIPIENT_ETH_ADDRESS.into(), None, None)
+    )
+    .assert_success();
+    let res = call_deposit_with_tx_hash(&master_account, &contract, None);
+    assert_error_code(&res, "ERR_WRONG_EVENT_ADDRESS");
 
-    //println!("#1: {:#?}", _res.promise_results());
+    // Deposits to the replaced custodian are accepted during the grace period
+    let (master_account, contract) = init();
+    call!(
+        contract.user_account,
+        contract.set_eth_custodian_address(
+            RECIPIENT_ETH_ADDRESS.into(),
+            Some(PROOF_BLOCK_HEIGHT),
+            None
+        )
+    )
+    .assert_success();
+    call_deposit(&master_account, &contract);
+    let balance = view!(contract.ft_balance_of(DEPOSITED_RECIPIENT.into())).unwrap_json::<u128>();
+    assert_eq!(balance, DEPOSITED_AMOUNT - DEPOSITED_FEE);
+
+    // The grace period can end at an explicit Ethereum block
+    let (master_account, contract) = init();
+    call!(
+        contract.user_account,
+        contract.set_eth_custodian_address(
+            RECIPIENT_ETH_ADDRESS.into(),
+            None,
+            Some(PROOF_BLOCK_HEIGHT - 1)
+        )
+    )
+    .assert_success();
+    let res = call_deposit_with_tx_hash(&master_account, &contract, None);
+    assert_error_code(&res, "ERR_WRONG_EVENT_ADDRESS");
+
+    let (master_account, contract) = init();
+    call!(
+        contract.user_account,
+        contract.set_eth_custodian_address(
+            RECIPIENT_ETH_ADDRESS.into(),
+            None,
+            Some(PROOF_BLOCK_HEIGHT)
+        )
+    )
+    .assert_success();
+    call_deposit(&master_account, &contract);
+    let balance = view!(contract.ft_balance_of(DEPOSITED_RECIPIENT.into())).unwrap_json::<u128>();
+    assert_eq!(balance, DEPOSITED_AMOUNT - DEPOSITED_FEE);
+}
+
+#[test]
+fn test_set_prover_account() {
+    let (master_account, contract) = init();
+    call_deposit_with_tx_hash(&master_account, &contract, Some(DEPOSIT_TX_HASH.into()));
+    let receipt = view!(contract.find_deposit(Some(call_proof_key(&contract)), None))
+        .unwrap_borsh::<Option<DepositReceipt>>()
+        .unwrap();
+
+    let res = call!(
+        master_account,
+        contract.set_prover_account("new_prover.root".into())
+    );
+    assert!(!res.is_ok());
+    let res = call!(
+        contract.user_account,
+        contract.set_prover_account("New Prover".into())
+    );
+    assert!(!res.is_ok());
+    call!(
+        contract.user_account,
+        contract.set_prover_account("new_prover.root".into())
+    )
+    .assert_success();
+
+    // The proof stays attributed to the prover which verified it, and used
+    let prover = view!(contract.get_proof_prover(receipt.proof_key.clone()))
+        .unwrap_borsh::<Option<AccountId>>();
+    assert_eq!(prover, Some(PROVER_ACCOUNT.into()));
+    let used = view!(contract.is_used_event(receipt.proof_key)).unwrap_borsh::<bool>();
+    assert!(used);
+}
+
+#[test]
+fn test_test_vectors() {
+    let (_, contract) = init();
+    let vectors = view!(contract.get_test_vectors()).unwrap_borsh::<Vec<TestVector>>();
+    let names: Vec<&str> = vectors.iter().map(|v| v.name.as_str()).collect();
+    assert_eq!(
+        names,
+        vec!["deposited_event", "withdraw_result", "proof_key"]
+    );
+    // Borsh: little-endian u128 amount, recipient, custodian and memo
+    let expected = format!(
+        "583e0f{}{}{}{}",
+        "00".repeat(13),
+        "33".repeat(20),
+        "11".repeat(20),
+        "44".repeat(32)
+    );
+    assert_eq!(vectors[1].output, expected);
 }
 
 #[test]
@@ -136,6 +571,15 @@ fn test_ft_total_supply() {
     assert_eq!(balance, DEPOSITED_AMOUNT);
 }
 
+#[test]
+fn test_ft_metadata() {
+    let (_, contract) = init();
+    let metadata = view!(contract.ft_metadata()).unwrap_json_value();
+    assert_eq!(metadata["spec"], "ft-1.0.0");
+    assert_eq!(metadata["symbol"], "nETH");
+    assert_eq!(metadata["decimals"], 18);
+}
+
 #[test]
 fn test_ft_transfer() {
     let (master_account, contract) = init();
@@ -198,3 +642,1433 @@ fn test_ft_transfer_call() {
         DEPOSITED_AMOUNT - DEPOSITED_FEE - 1 * transfer_amount as u128
     );
 }
+
+#[test]
+fn test_deposit_paused() {
+    let (master_account, contract) = init();
+
+    call!(
+        contract.user_account,
+        contract.set_paused_flags(PAUSE_DEPOSIT)
+    )
+    .assert_success();
+    let paused_mask = view!(contract.get_paused_flags()).unwrap_borsh::<u8>();
+    assert_eq!(paused_mask, PAUSE_DEPOSIT);
+
+    let res = call_deposit_with_tx_hash(&master_account, &contract, None);
+    assert_error_code(&res, "ERR_PAUSED");
+    let balance = view!(contract.ft_balance_of(DEPOSITED_RECIPIENT.into())).unwrap_json::<u128>();
+    assert_eq!(balance, 0);
+
+    call!(contract.user_account, contract.set_paused_flags(0)).assert_success();
+    call_deposit(&master_account, &contract);
+    let balance = view!(contract.ft_balance_of(DEPOSITED_RECIPIENT.into())).unwrap_json::<u128>();
+    assert_eq!(balance, DEPOSITED_AMOUNT - DEPOSITED_FEE);
+}
+
+#[test]
+fn test_withdraw_paused() {
+    let (master_account, contract) = init();
+    call_deposit(&master_account, &contract);
+
+    call!(
+        contract.user_account,
+        contract.set_paused_flags(PAUSE_WITHDRAW)
+    )
+    .assert_success();
+    let res = call!(
+        master_account,
+        contract.withdraw(RECIPIENT_ETH_ADDRESS.into(), 100),
+        gas = DEFAULT_GAS * 3
+    );
+    assert!(!res.is_ok());
+
+    let balance = view!(contract.ft_balance_of(DEPOSITED_RECIPIENT.into())).unwrap_json::<u128>();
+    assert_eq!(balance, DEPOSITED_AMOUNT - DEPOSITED_FEE);
+}
+
+#[test]
+fn test_refusal_policy() {
+    let (master_account, contract) = init();
+    call_deposit(&master_account, &contract);
+    call!(
+        contract.user_account,
+        contract.set_eth_recipient_blocked(RECIPIENT_ETH_ADDRESS.into(), true)
+    )
+    .assert_success();
+    call!(
+        contract.user_account,
+        contract.set_refusal_policy(REFUSE_BLOCKLISTED)
+    )
+    .assert_success();
+    let refusal_mask = view!(contract.get_refusal_policy()).unwrap_borsh::<u8>();
+    assert_eq!(refusal_mask, REFUSE_BLOCKLISTED);
+
+    let res = call!(
+        master_account,
+        contract.withdraw(RECIPIENT_ETH_ADDRESS.into(), 100),
+        gas = DEFAULT_GAS * 3
+    );
+    res.assert_success();
+    assert_eq!(
+        res.unwrap_borsh::<GuardRefusal>(),
+        GuardRefusal {
+            guard: REFUSE_BLOCKLISTED,
+            error: "ERR_BLOCKED_ETH_RECIPIENT".into(),
+        }
+    );
+    let balance = view!(contract.ft_balance_of(DEPOSITED_RECIPIENT.into())).unwrap_json::<u128>();
+    assert_eq!(balance, DEPOSITED_AMOUNT - DEPOSITED_FEE);
+
+    // Other guard classes still panic
+    call!(
+        contract.user_account,
+        contract.set_paused_flags(PAUSE_WITHDRAW)
+    )
+    .assert_success();
+    let res = call!(
+        master_account,
+        contract.withdraw(RECIPIENT_ETH_ADDRESS.into(), 100),
+        gas = DEFAULT_GAS * 3
+    );
+    assert!(!res.is_ok());
+}
+
+#[test]
+fn test_withdraw_checksummed_address() {
+    let (master_account, contract) = init();
+    call_deposit(&master_account, &contract);
+
+    // Wrong case of the first `B`
+    let res = call!(
+        master_account,
+        contract.withdraw("0x891b2749238B27fF58e951088e55b04de71Dc374".into(), 100),
+        gas = DEFAULT_GAS * 3
+    );
+    assert!(!res.is_ok());
+    call!(
+        master_account,
+        contract.withdraw("0x891B2749238B27fF58e951088e55b04de71Dc374".into(), 100),
+        gas = DEFAULT_GAS * 3
+    )
+    .assert_success();
+    let history = view!(contract.get_account_history(DEPOSITED_RECIPIENT.into(), 0, 1))
+        .unwrap_borsh::<Vec<HistoryEntry>>();
+    assert_eq!(history[0].eth_address, RECIPIENT_ETH_ADDRESS);
+}
+
+#[test]
+fn test_withdraw_fee_and_minimum() {
+    let (master_account, contract) = init();
+    call_deposit(&master_account, &contract);
+    call!(contract.user_account, contract.set_withdraw_fee(10, 1000)).assert_success();
+
+    let res = call!(
+        master_account,
+        contract.withdraw(RECIPIENT_ETH_ADDRESS.into(), 999),
+        gas = DEFAULT_GAS * 3
+    );
+    assert_error_code(&res, "ERR_WITHDRAW_BELOW_MINIMUM");
+
+    call!(
+        master_account,
+        contract.withdraw(RECIPIENT_ETH_ADDRESS.into(), 1500),
+        gas = DEFAULT_GAS * 3
+    )
+    .assert_success();
+    let balance = view!(contract.ft_balance_of(DEPOSITED_RECIPIENT.into())).unwrap_json::<u128>();
+    assert_eq!(balance, DEPOSITED_AMOUNT - DEPOSITED_FEE - 1500);
+    let fee_config = view!(contract.get_fee_config()).unwrap_borsh::<FeeConfig>();
+    assert_eq!(fee_config.accrued_fees, 2);
+}
+
+#[test]
+fn test_effective_limits() {
+    let (master_account, contract) = init();
+    call_deposit(&master_account, &contract);
+    call!(contract.user_account, contract.set_withdraw_fee(10, 1000)).assert_success();
+    let limits = view!(contract.get_effective_limits(DEPOSITED_RECIPIENT.into()))
+        .unwrap_borsh::<EffectiveLimits>();
+    assert_eq!(
+        limits,
+        EffectiveLimits {
+            balance: DEPOSITED_AMOUNT - DEPOSITED_FEE,
+            deposit_paused: false,
+            withdraw_paused: false,
+            min_withdraw_amount: 1000,
+            max_withdraw_amount: DEPOSITED_AMOUNT - DEPOSITED_FEE,
+            withdraw_fee_bps: 10,
+        }
+    );
+
+    call!(
+        contract.user_account,
+        contract.set_paused_flags(PAUSE_WITHDRAW)
+    )
+    .assert_success();
+    let limits = view!(contract.get_effective_limits(DEPOSITED_RECIPIENT.into()))
+        .unwrap_borsh::<EffectiveLimits>();
+    assert!(limits.withdraw_paused);
+    assert_eq!(limits.max_withdraw_amount, 0);
+}
+
+#[test]
+fn test_accounts_counter() {
+    let (master_account, contract) = init();
+    let counter = view!(contract.get_accounts_counter()).unwrap_borsh::<u64>();
+    assert_eq!(counter, 1);
+
+    call_deposit(&master_account, &contract);
+    let counter = view!(contract.get_accounts_counter()).unwrap_borsh::<u64>();
+    assert_eq!(counter, 2);
+}
+
+#[test]
+fn test_deposit_fee_split_with_relayer() {
+    let (master_account, contract) = init();
+    call!(contract.user_account, contract.set_relayer_fee_share(5_000)).assert_success();
+
+    // The master account relays the proof and is also the deposit recipient
+    call_deposit(&master_account, &contract);
+    let relayer_fee = DEPOSITED_FEE / 2;
+    let balance = view!(contract.ft_balance_of(DEPOSITED_RECIPIENT.into())).unwrap_json::<u128>();
+    assert_eq!(balance, DEPOSITED_AMOUNT - DEPOSITED_FEE + relayer_fee);
+
+    let balance = view!(contract.ft_balance_of(CONTRACT_ACC.into())).unwrap_json::<u128>();
+    assert_eq!(balance, DEPOSITED_FEE - relayer_fee);
+}
+
+#[test]
+fn test_deposit_protocol_fee() {
+    let (master_account, contract) = init();
+    let res = call!(contract.user_account, contract.set_deposit_fee(1_001));
+    assert!(!res.is_ok());
+    let res = call!(master_account, contract.set_deposit_fee(30));
+    assert!(!res.is_ok());
+    call!(contract.user_account, contract.set_deposit_fee(30)).assert_success();
+
+    call_deposit(&master_account, &contract);
+    let protocol_fee = 150;
+    let balance = view!(contract.ft_balance_of(DEPOSITED_RECIPIENT.into())).unwrap_json::<u128>();
+    assert_eq!(balance, DEPOSITED_AMOUNT - DEPOSITED_FEE - protocol_fee);
+    let fee_config = view!(contract.get_fee_config()).unwrap_borsh::<FeeConfig>();
+    assert_eq!(
+        fee_config,
+        FeeConfig {
+            deposit_fee_bps: 30,
+            relayer_fee_share_bps: 0,
+            withdraw_fee_bps: 0,
+            min_withdraw_amount: 0,
+            accrued_fees: protocol_fee,
+        }
+    );
+
+    let res = call!(
+        master_account,
+        contract.claim_fees(master_account.account_id())
+    );
+    assert!(!res.is_ok());
+    call!(
+        contract.user_account,
+        contract.claim_fees(master_account.account_id())
+    )
+    .assert_success();
+    let balance = view!(contract.ft_balance_of(DEPOSITED_RECIPIENT.into())).unwrap_json::<u128>();
+    assert_eq!(balance, DEPOSITED_AMOUNT - DEPOSITED_FEE);
+    let fee_config = view!(contract.get_fee_config()).unwrap_borsh::<FeeConfig>();
+    assert_eq!(fee_config.accrued_fees, 0);
+    let total_supply = view!(contract.ft_total_supply()).unwrap_json::<u128>();
+    assert_eq!(total_supply, DEPOSITED_AMOUNT);
+}
+
+#[test]
+fn test_maintenance_mode() {
+    let (master_account, contract) = init();
+    call!(
+        contract.user_account,
+        contract.set_maintenance_mode(Some("Migration in progress".into()))
+    )
+    .assert_success();
+
+    let res = call_deposit_with_tx_hash(&master_account, &contract, None);
+    assert_error_code(&res, "ERR_MAINTENANCE");
+    let balance = view!(contract.ft_balance_of(DEPOSITED_RECIPIENT.into())).unwrap_json::<u128>();
+    assert_eq!(balance, 0);
+
+    call!(contract.user_account, contract.set_maintenance_mode(None)).assert_success();
+    call_deposit(&master_account, &contract);
+    let balance = view!(contract.ft_balance_of(DEPOSITED_RECIPIENT.into())).unwrap_json::<u128>();
+    assert_eq!(balance, DEPOSITED_AMOUNT - DEPOSITED_FEE);
+}
+
+#[test]
+fn test_last_eth_block_height() {
+    let (master_account, contract) = init();
+    let height = view!(contract.get_last_eth_block_height()).unwrap_borsh::<u64>();
+    assert_eq!(height, 0);
+
+    call_deposit(&master_account, &contract);
+    let height = view!(contract.get_last_eth_block_height()).unwrap_borsh::<u64>();
+    assert_eq!(height, PROOF_BLOCK_HEIGHT);
+}
+
+#[test]
+fn test_find_deposit() {
+    let (master_account, contract) = init();
+    let receipt = view!(contract.find_deposit(Some(call_proof_key(&contract)), None))
+        .unwrap_borsh::<Option<DepositReceipt>>();
+    assert!(receipt.is_none());
+
+    call_deposit_with_tx_hash(&master_account, &contract, Some(DEPOSIT_TX_HASH.into()));
+    let receipt = view!(contract.find_deposit(Some(call_proof_key(&contract)), None))
+        .unwrap_borsh::<Option<DepositReceipt>>()
+        .unwrap();
+    assert_eq!(receipt.new_owner_id, DEPOSITED_RECIPIENT);
+    assert_eq!(receipt.amount, DEPOSITED_AMOUNT);
+    assert_eq!(receipt.fee, DEPOSITED_FEE);
+    assert_eq!(receipt.eth_block_height, PROOF_BLOCK_HEIGHT);
+    assert_eq!(receipt.relayer_id, master_account.account_id());
+    let gas_budget = view!(contract.get_gas_budget()).unwrap_borsh::<GasBudget>();
+    assert_eq!(receipt.prover_gas, gas_budget.verify_log_entry);
+    assert_eq!(receipt.finish_deposit_gas, gas_budget.finish_deposit);
+    // Kept as given, the proof doesn't commit to it
+    let eth_tx_hash: String = receipt
+        .eth_tx_hash
+        .unwrap()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect();
+    assert_eq!(format!("0x{}", eth_tx_hash), DEPOSIT_TX_HASH);
+
+    let submitter = view!(contract.get_proof_submitter(receipt.proof_key.clone()))
+        .unwrap_borsh::<Option<AccountId>>();
+    assert_eq!(submitter, Some(master_account.account_id()));
+
+    let by_trace_id = view!(contract.find_deposit(None, Some(receipt.trace_id.clone())))
+        .unwrap_borsh::<Option<DepositReceipt>>()
+        .unwrap();
+    assert_eq!(by_trace_id.proof_key, receipt.proof_key);
+}
+
+#[test]
+fn test_compact_proof_key() {
+    let (master_account, contract) = init();
+    call_deposit_with_tx_hash(&master_account, &contract, Some(DEPOSIT_TX_HASH.into()));
+    let receipt = view!(contract.find_deposit(Some(call_proof_key(&contract)), None))
+        .unwrap_borsh::<Option<DepositReceipt>>()
+        .unwrap();
+    // Hex encoded sha256 of the block hash and indexes
+    assert_eq!(receipt.proof_key.len(), 64);
+    assert!(receipt.proof_key.chars().all(|c| c.is_ascii_hexdigit()));
+    let used = view!(contract.is_used_event(receipt.proof_key)).unwrap_borsh::<bool>();
+    assert!(used);
+    assert!(call_is_used_proof(&contract));
+}
+
+#[test]
+fn test_deposit_storage_deposit() {
+    let (master_account, contract) = init();
+    let res = call_deposit_attaching(
+        &master_account,
+        &contract,
+        None,
+        USED_PROOF_STORAGE_DEPOSIT - 1,
+    );
+    assert_error_code(&res, "ERR_INSUFFICIENT_STORAGE_DEPOSIT");
+
+    // The surplus is refunded
+    let balance = master_account.account().unwrap().amount;
+    call_deposit_attaching(&master_account, &contract, None, to_yocto("1")).assert_success();
+    let spent = balance - master_account.account().unwrap().amount;
+    assert!(spent >= USED_PROOF_STORAGE_DEPOSIT);
+    assert!(spent < to_yocto("0.5"));
+}
+
+#[test]
+fn test_deposit_storage_cost_measured() {
+    let (master_account, contract) = init();
+    let cost = view!(contract.get_deposit_storage_cost()).unwrap_json::<u128>();
+    assert_eq!(cost, USED_PROOF_STORAGE_DEPOSIT);
+    // The first record is measured and scaled to the canonical record size
+    call_deposit(&master_account, &contract);
+    let cost = view!(contract.get_deposit_storage_cost()).unwrap_json::<u128>();
+    assert_eq!(cost, USED_PROOF_STORAGE_DEPOSIT);
+}
+
+#[test]
+fn test_prune_used_events() {
+    let (master_account, contract) = init();
+    call!(
+        contract.user_account,
+        contract.set_code_hash_expectation("11".repeat(32))
+    )
+    .assert_success();
+    // Put the highest accepted block one finality horizon past the proof block
+    let args = serde_json::json!({
+        "accounts": [],
+        "used_proofs": [],
+        "last_eth_block_height": PROOF_BLOCK_HEIGHT + 1 + PRUNE_HORIZON_BLOCKS,
+    });
+    contract
+        .user_account
+        .call(
+            contract.account_id(),
+            "migrate",
+            args.to_string().as_bytes(),
+            DEFAULT_GAS,
+            0,
+        )
+        .assert_success();
+    call_deposit(&master_account, &contract);
+
+    let res = call!(
+        master_account,
+        contract.prune_used_events(PROOF_BLOCK_HEIGHT + 1, 10, None)
+    );
+    assert!(!res.is_ok());
+    let res = call!(
+        contract.user_account,
+        contract.prune_used_events(PROOF_BLOCK_HEIGHT + 2, 10, None)
+    );
+    assert!(!res.is_ok());
+    let res = call!(
+        contract.user_account,
+        contract.prune_used_events(PROOF_BLOCK_HEIGHT + 1, 10, None)
+    );
+    res.assert_success();
+    assert_eq!(res.unwrap_borsh::<u64>(), 1);
+    assert!(!call_is_used_proof(&contract));
+
+    // The pruned proof can't be replayed
+    let res = call_deposit_with_tx_hash(&master_account, &contract, None);
+    assert_error_code(&res, "ERR_PROOF_TOO_OLD");
+}
+
+fn call_proof_key(contract: &ContractAccount<EthConnectorContract>) -> String {
+    let proof: Proof = serde_json::from_str(PROOF_DATA).unwrap();
+    view!(contract.get_proof_key(
+        proof.log_index,
+        proof.log_entry_data,
+        proof.receipt_index,
+        proof.receipt_data,
+        proof.header_data,
+        proof.proof,
+        proof.skip_bridge_call
+    ))
+    .unwrap_borsh::<String>()
+}
+
+fn call_is_used_proof(contract: &ContractAccount<EthConnectorContract>) -> bool {
+    let proof: Proof = serde_json::from_str(PROOF_DATA).unwrap();
+    view!(contract.is_used_proof(
+        proof.log_index,
+        proof.log_entry_data,
+        proof.receipt_index,
+        proof.receipt_data,
+        proof.header_data,
+        proof.proof,
+        proof.skip_bridge_call
+    ))
+    .unwrap_borsh::<bool>()
+}
+
+#[test]
+fn test_complete_deposit_not_deferred() {
+    let (master_account, contract) = init();
+    call_deposit_with_tx_hash(&master_account, &contract, Some(DEPOSIT_TX_HASH.into()));
+    let receipt = view!(contract.find_deposit(Some(call_proof_key(&contract)), None))
+        .unwrap_borsh::<Option<DepositReceipt>>()
+        .unwrap();
+    // The deposit was minted by `finish_deposit`, it can't be minted again
+    let res = call!(master_account, contract.complete_deposit(receipt.proof_key));
+    assert!(!res.is_ok());
+    let balance = view!(contract.ft_balance_of(DEPOSITED_RECIPIENT.into())).unwrap_json::<u128>();
+    assert_eq!(balance, DEPOSITED_AMOUNT - DEPOSITED_FEE);
+}
+
+#[test]
+fn test_is_used_proof() {
+    let (master_account, contract) = init();
+    assert!(!call_is_used_proof(&contract));
+    call_deposit_with_tx_hash(&master_account, &contract, Some(DEPOSIT_TX_HASH.into()))
+        .assert_success();
+    assert!(call_is_used_proof(&contract));
+
+    let receipt = view!(contract.find_deposit(Some(call_proof_key(&contract)), None))
+        .unwrap_borsh::<Option<DepositReceipt>>()
+        .unwrap();
+    let used = view!(contract.is_used_event(receipt.proof_key)).unwrap_borsh::<bool>();
+    assert!(used);
+    let used = view!(contract.is_used_event("123".into())).unwrap_borsh::<bool>();
+    assert!(!used);
+}
+
+#[test]
+fn test_deposit_if_unused() {
+    let (master_account, contract) = init();
+    call_deposit(&master_account, &contract);
+
+    let proof: Proof = serde_json::from_str(PROOF_DATA).unwrap();
+    let res = call!(
+        master_account,
+        contract.deposit_if_unused(
+            proof.log_index,
+            proof.log_entry_data,
+            proof.receipt_index,
+            proof.receipt_data,
+            proof.header_data,
+            proof.proof,
+            proof.skip_bridge_call
+        ),
+        gas = DEFAULT_GAS * 3
+    );
+    res.assert_success();
+    assert_eq!(
+        res.unwrap_borsh::<DepositStatus>(),
+        DepositStatus::AlreadyUsed
+    );
+
+    let balance = view!(contract.ft_balance_of(DEPOSITED_RECIPIENT.into())).unwrap_json::<u128>();
+    assert_eq!(balance, DEPOSITED_AMOUNT - DEPOSITED_FEE);
+}
+
+fn call_mock_deposit(
+    master_account: &UserAccount,
+    contract: &ContractAccount<EthConnectorContract>,
+    verified: Option<bool>,
+) -> ExecutionResult {
+    let proof: Proof = serde_json::from_str(PROOF_DATA).unwrap();
+    call!(
+        master_account,
+        contract.mock_deposit(
+            proof.log_index,
+            proof.log_entry_data,
+            proof.receipt_index,
+            proof.receipt_data,
+            proof.header_data,
+            proof.proof,
+            proof.skip_bridge_call,
+            verified
+        ),
+        deposit = USED_PROOF_STORAGE_DEPOSIT,
+        gas = DEFAULT_GAS * 3
+    )
+}
+
+#[test]
+fn test_mock_deposit_verified() {
+    let (master_account, contract) = init();
+    let res = call_mock_deposit(&master_account, &contract, Some(true));
+    res.assert_success();
+    // Minted by the callback run within the same call
+    assert_eq!(res.promise_results().len(), 1);
+    assert_eq!(
+        res.unwrap_borsh::<DepositStatus>(),
+        DepositStatus::Deposited
+    );
+    let balance = view!(contract.ft_balance_of(DEPOSITED_RECIPIENT.into())).unwrap_json::<u128>();
+    assert_eq!(balance, DEPOSITED_AMOUNT - DEPOSITED_FEE);
+    assert!(call_is_used_proof(&contract));
+
+    let res = call_mock_deposit(&master_account, &contract, Some(true));
+    assert_eq!(
+        res.unwrap_borsh::<DepositStatus>(),
+        DepositStatus::AlreadyUsed
+    );
+}
+
+#[test]
+fn test_mock_deposit_rejected() {
+    let (master_account, contract) = init();
+    let res = call_mock_deposit(&master_account, &contract, Some(false));
+    assert_error_code(&res, "ERR_VERIFY_PROOF");
+    let balance = view!(contract.ft_balance_of(DEPOSITED_RECIPIENT.into())).unwrap_json::<u128>();
+    assert_eq!(balance, 0);
+    assert!(!call_is_used_proof(&contract));
+}
+
+#[test]
+fn test_mock_deposit_prover_failed() {
+    let (master_account, contract) = init();
+    let res = call_mock_deposit(&master_account, &contract, None);
+    assert_eq!(
+        res.unwrap_borsh::<DepositStatus>(),
+        DepositStatus::Retryable
+    );
+    assert!(!call_is_used_proof(&contract));
+
+    let res = call_mock_deposit(&master_account, &contract, Some(true));
+    assert_eq!(
+        res.unwrap_borsh::<DepositStatus>(),
+        DepositStatus::Deposited
+    );
+    let balance = view!(contract.ft_balance_of(DEPOSITED_RECIPIENT.into())).unwrap_json::<u128>();
+    assert_eq!(balance, DEPOSITED_AMOUNT - DEPOSITED_FEE);
+}
+
+#[test]
+fn test_account_history() {
+    let (master_account, contract) = init();
+    call_deposit(&master_account, &contract);
+    call!(
+        master_account,
+        contract.withdraw(RECIPIENT_ETH_ADDRESS.into(), 100),
+        gas = DEFAULT_GAS * 3
+    )
+    .assert_success();
+
+    let history = view!(contract.get_account_history(DEPOSITED_RECIPIENT.into(), 0, 10))
+        .unwrap_borsh::<Vec<HistoryEntry>>();
+    assert_eq!(history.len(), 2);
+    assert_eq!(history[0].kind, HistoryKind::Withdraw);
+    assert_eq!(history[0].amount, 100);
+    assert_eq!(history[0].eth_address, RECIPIENT_ETH_ADDRESS);
+    assert_eq!(history[1].kind, HistoryKind::Deposit);
+    assert_eq!(history[1].amount, DEPOSITED_AMOUNT);
+
+    let history = view!(contract.get_account_history(DEPOSITED_RECIPIENT.into(), 1, 10))
+        .unwrap_borsh::<Vec<HistoryEntry>>();
+    assert_eq!(history.len(), 1);
+}
+
+fn call_preflight(contract: &ContractAccount<EthConnectorContract>) -> PreflightReport {
+    let proof: Proof = serde_json::from_str(PROOF_DATA).unwrap();
+    view!(contract.preflight(
+        proof.log_index,
+        proof.log_entry_data,
+        proof.receipt_index,
+        proof.receipt_data,
+        proof.header_data,
+        proof.proof,
+        proof.skip_bridge_call
+    ))
+    .unwrap_borsh::<PreflightReport>()
+}
+
+#[test]
+fn test_preflight() {
+    let (master_account, contract) = init();
+    let report = call_preflight(&contract);
+    assert!(report.failures.is_empty());
+    assert!(report.required_gas > 0);
+
+    call_deposit(&master_account, &contract);
+    call!(
+        contract.user_account,
+        contract.set_paused_flags(PAUSE_DEPOSIT)
+    )
+    .assert_success();
+    let report = call_preflight(&contract);
+    assert_eq!(
+        report.failures,
+        vec![
+            PreflightFailure::DepositPaused,
+            PreflightFailure::ProofAlreadyUsed
+        ]
+    );
+}
+
+#[test]
+fn test_withdraw_for_executor() {
+    let (master_account, contract) = init();
+    call_deposit(&master_account, &contract);
+    let executor = master_account.create_user("executor".to_string(), to_yocto("10"));
+
+    call!(
+        master_account,
+        contract.approve_withdraw_executor(executor.account_id(), 100),
+        deposit = to_yocto("0.1")
+    )
+    .assert_success();
+    call!(
+        executor,
+        contract.withdraw_for(DEPOSITED_RECIPIENT.into(), RECIPIENT_ETH_ADDRESS.into(), 60)
+    )
+    .assert_success();
+    let balance = view!(contract.ft_balance_of(DEPOSITED_RECIPIENT.into())).unwrap_json::<u128>();
+    assert_eq!(balance, DEPOSITED_AMOUNT - DEPOSITED_FEE - 60);
+    let allowance =
+        view!(contract.get_withdraw_allowance(DEPOSITED_RECIPIENT.into(), executor.account_id()))
+            .unwrap_json::<u128>();
+    assert_eq!(allowance, 40);
+
+    // Exceeding the remaining allowance fails
+    let res = call!(
+        executor,
+        contract.withdraw_for(DEPOSITED_RECIPIENT.into(), RECIPIENT_ETH_ADDRESS.into(), 60)
+    );
+    assert!(!res.is_ok());
+    assert_error_code(&res, "ERR_ALLOWANCE_EXCEEDED");
+
+    // A refused withdrawal keeps the allowance
+    call!(
+        contract.user_account,
+        contract.set_eth_recipient_blocked(RECIPIENT_ETH_ADDRESS.into(), true)
+    )
+    .assert_success();
+    call!(
+        contract.user_account,
+        contract.set_refusal_policy(REFUSE_BLOCKLISTED)
+    )
+    .assert_success();
+    let res = call!(
+        executor,
+        contract.withdraw_for(DEPOSITED_RECIPIENT.into(), RECIPIENT_ETH_ADDRESS.into(), 40)
+    );
+    res.assert_success();
+    assert_eq!(
+        res.unwrap_borsh::<GuardRefusal>(),
+        GuardRefusal {
+            guard: REFUSE_BLOCKLISTED,
+            error: "ERR_BLOCKED_ETH_RECIPIENT".into(),
+        }
+    );
+    let allowance =
+        view!(contract.get_withdraw_allowance(DEPOSITED_RECIPIENT.into(), executor.account_id()))
+            .unwrap_json::<u128>();
+    assert_eq!(allowance, 40);
+
+    call!(
+        master_account,
+        contract.revoke_withdraw_executor(executor.account_id())
+    )
+    .assert_success();
+    let allowance =
+        view!(contract.get_withdraw_allowance(DEPOSITED_RECIPIENT.into(), executor.account_id()))
+            .unwrap_json::<u128>();
+    assert_eq!(allowance, 0);
+}
+
+#[test]
+fn test_withdraw_intent() {
+    let (master_account, contract) = init();
+    call_deposit(&master_account, &contract);
+    let keeper = master_account.create_user("keeper".to_string(), to_yocto("10"));
+
+    let res = call!(
+        master_account,
+        contract.authorize_withdraw_intent(
+            Some(keeper.account_id()),
+            RECIPIENT_ETH_ADDRESS.into(),
+            100,
+            u64::MAX / 2
+        ),
+        deposit = to_yocto("0.1")
+    );
+    res.assert_success();
+    let id = res.unwrap_borsh::<u64>();
+    call!(keeper, contract.execute_withdraw_intent(id, 60)).assert_success();
+    let balance = view!(contract.ft_balance_of(DEPOSITED_RECIPIENT.into())).unwrap_json::<u128>();
+    assert_eq!(balance, DEPOSITED_AMOUNT - DEPOSITED_FEE - 60);
+
+    // Only the keeper executes it, within the remaining amount
+    let res = call!(master_account, contract.execute_withdraw_intent(id, 40));
+    assert!(!res.is_ok());
+    let res = call!(keeper, contract.execute_withdraw_intent(id, 60));
+    assert_error_code(&res, "ERR_ALLOWANCE_EXCEEDED");
+
+    // A fully used intent is removed
+    call!(keeper, contract.execute_withdraw_intent(id, 40)).assert_success();
+    let intent = view!(contract.get_withdraw_intent(id)).unwrap_borsh::<Option<WithdrawIntent>>();
+    assert!(intent.is_none());
+}
+
+#[test]
+fn test_withdraw_intent_refused() {
+    let (master_account, contract) = init();
+    call_deposit(&master_account, &contract);
+    let res = call!(
+        master_account,
+        contract.authorize_withdraw_intent(None, RECIPIENT_ETH_ADDRESS.into(), 100, u64::MAX / 2),
+        deposit = to_yocto("0.1")
+    );
+    res.assert_success();
+    let id = res.unwrap_borsh::<u64>();
+    call!(
+        contract.user_account,
+        contract.set_eth_recipient_blocked(RECIPIENT_ETH_ADDRESS.into(), true)
+    )
+    .assert_success();
+    call!(
+        contract.user_account,
+        contract.set_refusal_policy(REFUSE_BLOCKLISTED)
+    )
+    .assert_success();
+
+    let res = call!(master_account, contract.execute_withdraw_intent(id, 100));
+    res.assert_success();
+    assert_eq!(
+        res.unwrap_borsh::<GuardRefusal>(),
+        GuardRefusal {
+            guard: REFUSE_BLOCKLISTED,
+            error: "ERR_BLOCKED_ETH_RECIPIENT".into(),
+        }
+    );
+    // Nothing was burned, so the intent keeps its amount
+    let balance = view!(contract.ft_balance_of(DEPOSITED_RECIPIENT.into())).unwrap_json::<u128>();
+    assert_eq!(balance, DEPOSITED_AMOUNT - DEPOSITED_FEE);
+    let intent = view!(contract.get_withdraw_intent(id))
+        .unwrap_borsh::<Option<WithdrawIntent>>()
+        .unwrap();
+    assert_eq!(intent.remaining_amount, 100);
+}
+
+#[test]
+fn test_deposit_fee_split_rounding() {
+    let (master_account, contract) = init();
+    call!(contract.user_account, contract.set_relayer_fee_share(3_333)).assert_success();
+
+    call_deposit(&master_account, &contract);
+    // 450 * 3333 / 10000 = 149.985: the relayer payout rounds down, the treasury keeps the rest
+    let relayer_fee = 149;
+    let balance = view!(contract.ft_balance_of(DEPOSITED_RECIPIENT.into())).unwrap_json::<u128>();
+    assert_eq!(balance, DEPOSITED_AMOUNT - DEPOSITED_FEE + relayer_fee);
+
+    let balance = view!(contract.ft_balance_of(CONTRACT_ACC.into())).unwrap_json::<u128>();
+    assert_eq!(balance, DEPOSITED_FEE - relayer_fee);
+}
+
+#[test]
+fn test_event_topics_registry() {
+    let (master_account, contract) = init();
+    let topics = view!(contract.get_event_topics()).unwrap_borsh::<EventTopics>();
+    let deposited: String = topics
+        .deposited
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect();
+    assert_eq!(deposited, DEPOSITED_EVENT_TOPIC);
+
+    // Registry changes are timelocked
+    let res = call!(
+        contract.user_account,
+        contract.schedule_deposited_event_topic(DEPOSITED_EVENT_TOPIC.into(), 0)
+    );
+    assert!(!res.is_ok());
+
+    call_deposit(&master_account, &contract);
+    let balance = view!(contract.ft_balance_of(DEPOSITED_RECIPIENT.into())).unwrap_json::<u128>();
+    assert_eq!(balance, DEPOSITED_AMOUNT - DEPOSITED_FEE);
+}
+
+#[test]
+fn test_withdraw_to_blocked_eth_recipient() {
+    let (master_account, contract) = init();
+    call_deposit(&master_account, &contract);
+
+    call!(
+        contract.user_account,
+        contract.set_eth_recipient_blocked(RECIPIENT_ETH_ADDRESS.into(), true)
+    )
+    .assert_success();
+    let blocked = view!(contract.is_eth_recipient_blocked(RECIPIENT_ETH_ADDRESS.into()))
+        .unwrap_borsh::<bool>();
+    assert!(blocked);
+    let res = call!(
+        master_account,
+        contract.withdraw(RECIPIENT_ETH_ADDRESS.into(), 100),
+        gas = DEFAULT_GAS * 3
+    );
+    assert!(!res.is_ok());
+    assert_error_code(&res, "ERR_BLOCKED_ETH_RECIPIENT");
+
+    call!(
+        contract.user_account,
+        contract.set_eth_recipient_blocked(RECIPIENT_ETH_ADDRESS.into(), false)
+    )
+    .assert_success();
+    call!(
+        master_account,
+        contract.withdraw(RECIPIENT_ETH_ADDRESS.into(), 100),
+        gas = DEFAULT_GAS * 3
+    )
+    .assert_success();
+    let balance = view!(contract.ft_balance_of(DEPOSITED_RECIPIENT.into())).unwrap_json::<u128>();
+    assert_eq!(balance, DEPOSITED_AMOUNT - DEPOSITED_FEE - 100);
+}
+
+#[test]
+fn test_revoke_proof() {
+    let (master_account, contract) = init();
+    call_deposit_with_tx_hash(&master_account, &contract, Some(DEPOSIT_TX_HASH.into()));
+    let receipt = view!(contract.find_deposit(Some(call_proof_key(&contract)), None))
+        .unwrap_borsh::<Option<DepositReceipt>>()
+        .unwrap();
+
+    call!(
+        contract.user_account,
+        contract.revoke_proof(
+            receipt.proof_key.clone(),
+            "orphaned block".into(),
+            Some(DEPOSITED_RECIPIENT.into()),
+            1000
+        )
+    )
+    .assert_success();
+    let balance = view!(contract.ft_balance_of(DEPOSITED_RECIPIENT.into())).unwrap_json::<u128>();
+    assert_eq!(balance, DEPOSITED_AMOUNT - DEPOSITED_FEE - 1000);
+    let revoked = view!(contract.get_revoked_proof(receipt.proof_key.clone()))
+        .unwrap_borsh::<Option<RevokedProof>>()
+        .unwrap();
+    assert_eq!(revoked.evidence, "orphaned block");
+    assert_eq!(revoked.clawed_back, 1000);
+
+    // A proof is revoked only once
+    let res = call!(
+        contract.user_account,
+        contract.revoke_proof(receipt.proof_key, "orphaned block".into(), None, 0)
+    );
+    assert!(!res.is_ok());
+}
+
+#[test]
+fn test_reconcile() {
+    let (master_account, contract) = init();
+    call_deposit(&master_account, &contract);
+    call!(
+        master_account,
+        contract.withdraw(RECIPIENT_ETH_ADDRESS.into(), 100),
+        gas = DEFAULT_GAS * 3
+    )
+    .assert_success();
+
+    let report = call!(master_account, contract.reconcile()).unwrap_borsh::<ReconcileReport>();
+    assert_eq!(report.total_minted, DEPOSITED_AMOUNT);
+    assert_eq!(report.total_burned, 100);
+    assert_eq!(report.total_supply, DEPOSITED_AMOUNT - 100);
+    assert!(report.consistent);
+}
+
+#[test]
+fn test_operator_tasks() {
+    let (master_account, contract) = init();
+    call!(contract.user_account, contract.set_deposit_fee(30)).assert_success();
+    call_deposit(&master_account, &contract);
+    let tasks = view!(contract.get_operator_tasks()).unwrap_borsh::<OperatorTasks>();
+    assert!(tasks.stuck_submissions.is_empty());
+    assert_eq!(tasks.claimable_fees, 150);
+    assert!(tasks.supply_consistent);
+    assert!(tasks.storage_covered);
+    assert_eq!(tasks.shadow_prover_disagreements, 0);
+}
+
+#[test]
+fn test_status() {
+    let (master_account, contract) = init();
+    call_deposit(&master_account, &contract);
+    let delay = 3600 * 1_000_000_000;
+    call!(contract.user_account, contract.set_timelock_delay(delay)).assert_success();
+    let args = serde_json::json!({"action": "set_deposit_fee", "deposit_fee_bps": 30});
+    contract
+        .user_account
+        .call(
+            contract.account_id(),
+            "stage_action",
+            args.to_string().as_bytes(),
+            DEFAULT_GAS,
+            0,
+        )
+        .assert_success();
+
+    let status = view!(contract.get_status()).unwrap_borsh::<ConnectorStatus>();
+    assert_eq!(status.staged_actions_count, 1);
+    assert!(status.next_staged_unlock_at.unwrap() >= delay);
+    assert_eq!(status.stuck_submissions_count, 0);
+    assert_eq!(status.total_supply, DEPOSITED_AMOUNT);
+    assert!(status.supply_consistent);
+    assert!(status.storage_covered);
+}
+
+#[test]
+fn test_register_self() {
+    let (master_account, contract) = init();
+    let res = call!(master_account, contract.register_self());
+    assert!(!res.is_ok());
+    let res = call!(
+        master_account,
+        contract.set_registry(Some("registry".into()), 1)
+    );
+    assert!(!res.is_ok());
+    call!(
+        contract.user_account,
+        contract.set_registry(Some("registry".into()), 1)
+    )
+    .assert_success();
+
+    // The registry call itself fails, as no registry is deployed in the simulator
+    call!(master_account, contract.register_self());
+    let registry = view!(contract.get_registry())
+        .unwrap_borsh::<Option<RegistryConfig>>()
+        .unwrap();
+    assert_eq!(registry.account_id, "registry");
+    assert_eq!(registry.eth_chain_id, 1);
+    assert!(registry.last_registered_at > 0);
+
+    // Only the owner can refresh before the interval elapsed
+    let res = call!(master_account, contract.register_self());
+    assert!(!res.is_ok());
+    call!(contract.user_account, contract.register_self());
+}
+
+#[test]
+fn test_block_confirmations() {
+    let (master_account, contract) = init();
+    // The light client stub is ten blocks past the proof block
+    let res = call!(
+        master_account,
+        contract.set_block_confirmations(Some(PROVER_ACCOUNT.into()), 20)
+    );
+    assert!(!res.is_ok());
+    call!(
+        contract.user_account,
+        contract.set_block_confirmations(Some(PROVER_ACCOUNT.into()), 20)
+    )
+    .assert_success();
+    // Not a failure: the storage deposit is refunded for the retry
+    call_deposit_with_tx_hash(&master_account, &contract, None).assert_success();
+    assert!(!call_is_used_proof(&contract));
+    let proof: Proof = serde_json::from_str(PROOF_DATA).unwrap();
+    let res = call!(
+        master_account,
+        contract.deposit_if_unused(
+            proof.log_index,
+            proof.log_entry_data,
+            proof.receipt_index,
+            proof.receipt_data,
+            proof.header_data,
+            proof.proof,
+            proof.skip_bridge_call
+        ),
+        deposit = USED_PROOF_STORAGE_DEPOSIT,
+        gas = DEFAULT_GAS * 3
+    );
+    res.assert_success();
+    assert_eq!(
+        res.unwrap_borsh::<DepositStatus>(),
+        DepositStatus::NotConfirmed
+    );
+    assert!(!call_is_used_proof(&contract));
+    let balance = view!(contract.ft_balance_of(DEPOSITED_RECIPIENT.into())).unwrap_json::<u128>();
+    assert_eq!(balance, 0);
+
+    call!(
+        contract.user_account,
+        contract.set_block_confirmations(Some(PROVER_ACCOUNT.into()), 10)
+    )
+    .assert_success();
+    call_deposit(&master_account, &contract);
+    let balance = view!(contract.ft_balance_of(DEPOSITED_RECIPIENT.into())).unwrap_json::<u128>();
+    assert_eq!(balance, DEPOSITED_AMOUNT - DEPOSITED_FEE);
+}
+
+#[test]
+fn test_shadow_prover() {
+    let (master_account, contract) = init();
+    let res = call!(
+        master_account,
+        contract.set_shadow_prover(Some("prover.v2".into()))
+    );
+    assert!(!res.is_ok());
+    call!(
+        contract.user_account,
+        contract.set_shadow_prover(Some("prover.v2".into()))
+    )
+    .assert_success();
+    let shadow_prover = view!(contract.get_shadow_prover()).unwrap_borsh::<Option<ShadowProver>>();
+    assert_eq!(
+        shadow_prover,
+        Some(ShadowProver {
+            account_id: "prover.v2".into(),
+            agreements: 0,
+            disagreements: 0,
+            failures: 0,
+        })
+    );
+
+    call!(contract.user_account, contract.set_shadow_prover(None)).assert_success();
+    let shadow_prover = view!(contract.get_shadow_prover()).unwrap_borsh::<Option<ShadowProver>>();
+    assert_eq!(shadow_prover, None);
+}
+
+#[test]
+fn test_schedule_fees() {
+    let (_, contract) = init();
+    let call_set_withdraw_fee = |effective_at: u64| {
+        let args = serde_json::json!({
+            "withdraw_fee_bps": 20,
+            "min_withdraw_amount": 100,
+            "effective_at": effective_at,
+        });
+        contract.user_account.call(
+            contract.account_id(),
+            "set_withdraw_fee",
+            args.to_string().as_bytes(),
+            DEFAULT_GAS,
+            0,
+        )
+    };
+    let res = call_set_withdraw_fee(1);
+    assert!(!res.is_ok());
+    let effective_at = 4_000_000_000_000_000_000;
+    call_set_withdraw_fee(effective_at).assert_success();
+
+    let fee_config = view!(contract.get_fee_config()).unwrap_borsh::<FeeConfig>();
+    assert_eq!(fee_config.withdraw_fee_bps, 0);
+    let changes =
+        view!(contract.get_scheduled_changes()).unwrap_borsh::<Vec<ScheduledParamChange>>();
+    assert_eq!(changes.len(), 2);
+    assert!(changes.iter().all(|item| item.effective_at == effective_at));
+    assert_eq!(changes[0].change, ParamChange::WithdrawFee(20));
+    assert_eq!(changes[1].change, ParamChange::MinWithdrawAmount(100));
+
+    call!(
+        contract.user_account,
+        contract.irreversibly_freeze(FREEZE_FEES)
+    )
+    .assert_success();
+    let changes =
+        view!(contract.get_scheduled_changes()).unwrap_borsh::<Vec<ScheduledParamChange>>();
+    assert!(changes.is_empty());
+}
+
+#[test]
+fn test_irreversibly_freeze() {
+    let (master_account, contract) = init();
+    let res = call!(master_account, contract.irreversibly_freeze(FREEZE_FEES));
+    assert!(!res.is_ok());
+    call!(
+        contract.user_account,
+        contract.irreversibly_freeze(FREEZE_FEES)
+    )
+    .assert_success();
+    let frozen_mask = view!(contract.get_frozen_flags()).unwrap_borsh::<u8>();
+    assert_eq!(frozen_mask, FREEZE_FEES);
+
+    let res = call!(contract.user_account, contract.set_relayer_fee_share(5_000));
+    assert!(!res.is_ok());
+    let res = call!(contract.user_account, contract.set_deposit_fee(30));
+    assert!(!res.is_ok());
+
+    call!(
+        contract.user_account,
+        contract.irreversibly_freeze(FREEZE_CUSTODIAN | FREEZE_PROVER)
+    )
+    .assert_success();
+    let res = call!(
+        contract.user_account,
+        contract.set_eth_custodian_address(RECIPIENT_ETH_ADDRESS.into(), None, None)
+    );
+    assert!(!res.is_ok());
+    let res = call!(
+        contract.user_account,
+        contract.set_prover_account("prover.root".into())
+    );
+    assert!(!res.is_ok());
+    // Freezing can't be undone
+    call!(contract.user_account, contract.irreversibly_freeze(0)).assert_success();
+    let frozen_mask = view!(contract.get_frozen_flags()).unwrap_borsh::<u8>();
+    assert_eq!(frozen_mask, FREEZE_FEES | FREEZE_CUSTODIAN | FREEZE_PROVER);
+}
+
+#[test]
+fn test_migrate_json_input() {
+    let (_, contract) = init();
+    call!(
+        contract.user_account,
+        contract.set_code_hash_expectation("11".repeat(32))
+    )
+    .assert_success();
+    let args = serde_json::json!({
+        "accounts": [{"account_id": "alice", "balance": "1000000000000000000000001"}],
+        "used_proofs": ["123"],
+        "last_eth_block_height": 100,
+    });
+    contract
+        .user_account
+        .call(
+            contract.account_id(),
+            "migrate",
+            args.to_string().as_bytes(),
+            DEFAULT_GAS,
+            0,
+        )
+        .assert_success();
+    let balance = view!(contract.ft_balance_of("alice".into())).unwrap_json::<u128>();
+    assert_eq!(balance, 10u128.pow(24) + 1);
+    let total_supply = view!(contract.ft_total_supply()).unwrap_json::<u128>();
+    assert_eq!(total_supply, 10u128.pow(24) + 1);
+    let height = view!(contract.get_last_eth_block_height()).unwrap_borsh::<u64>();
+    assert_eq!(height, 100);
+
+    // The import is a one-shot cold start
+    let res = contract.user_account.call(
+        contract.account_id(),
+        "migrate",
+        args.to_string().as_bytes(),
+        DEFAULT_GAS,
+        0,
+    );
+    assert!(!res.is_ok());
+}
+
+#[test]
+fn test_register_alias() {
+    let (master_account, contract) = init();
+    let alice = master_account.create_user("alice".to_string(), to_yocto("10"));
+    // The registration is anchored on the light client head
+    let res = call!(
+        alice,
+        contract.register_alias("abc".into()),
+        deposit = to_yocto("0.1")
+    );
+    assert!(!res.is_ok());
+    call!(
+        contract.user_account,
+        contract.set_block_confirmations(Some(PROVER_ACCOUNT.into()), 10)
+    )
+    .assert_success();
+    call!(
+        alice,
+        contract.register_alias("abc".into()),
+        deposit = to_yocto("0.1")
+    )
+    .assert_success();
+    let account =
+        view!(contract.get_alias_account("abc".into())).unwrap_borsh::<Option<AccountId>>();
+    assert_eq!(account, Some(alice.account_id()));
+    let res = call!(
+        master_account,
+        contract.register_alias("abc".into()),
+        deposit = to_yocto("0.1")
+    );
+    assert!(!res.is_ok());
+
+    // The light client is past the proof block: the deposit predates the alias
+    let res = call_deposit_to(&master_account, &contract, "@abc");
+    assert_error_code(&res, "ERR_ALIAS_REGISTERED_LATE");
+    let balance = view!(contract.ft_balance_of(alice.account_id())).unwrap_json::<u128>();
+    assert_eq!(balance, 0);
+}
+
+#[test]
+fn test_deposit_to_alias() {
+    let (master_account, contract) = init();
+    call!(
+        contract.user_account,
+        contract.set_code_hash_expectation("11".repeat(32))
+    )
+    .assert_success();
+    let args = serde_json::json!({
+        "aliases": [{"alias": "abc", "account_id": "alice"}],
+    });
+    contract
+        .user_account
+        .call(
+            contract.account_id(),
+            "migrate",
+            args.to_string().as_bytes(),
+            DEFAULT_GAS,
+            0,
+        )
+        .assert_success();
+
+    // Unregistered aliases don't resolve
+    let res = call_deposit_to(&master_account, &contract, "@abd");
+    assert_error_code(&res, "ERR_UNREGISTERED_ALIAS");
+
+    call_deposit_to(&master_account, &contract, "@abc").assert_success();
+    let balance = view!(contract.ft_balance_of("alice".into())).unwrap_json::<u128>();
+    assert_eq!(balance, DEPOSITED_AMOUNT - DEPOSITED_FEE);
+}
+
+#[test]
+fn test_deposit_to_invalid_recipient() {
+    let (master_account, contract) = init();
+    let res = call_deposit_to(&master_account, &contract, "Root");
+    assert_error_code(&res, "ERR_INVALID_RECIPIENT");
+    assert!(!call_is_used_proof(&contract));
+}
+
+#[test]
+fn test_migrate_amount_overflow() {
+    let (_, contract) = init();
+    call!(
+        contract.user_account,
+        contract.set_code_hash_expectation("11".repeat(32))
+    )
+    .assert_success();
+    let args = serde_json::json!({
+        "accounts": [
+            {"account_id": "alice", "balance": u128::MAX.to_string()},
+            {"account_id": "bob", "balance": "1"},
+        ],
+        "used_proofs": [],
+    });
+    let res = contract.user_account.call(
+        contract.account_id(),
+        "migrate",
+        args.to_string().as_bytes(),
+        DEFAULT_GAS,
+        0,
+    );
+    assert_error_code(&res, "ERR_AMOUNT_OVERFLOW");
+}
+
+#[test]
+fn test_callback_without_promise_result() {
+    let (_, contract) = init();
+    let args = FinishDepositTransferCallArgs {
+        trace_id: "trace".into(),
+        receiver_id: DEPOSITED_RECIPIENT.into(),
+        amount: 10,
+    }
+    .try_to_vec()
+    .unwrap();
+    // Called directly by the contract itself, not as a callback of a promise
+    let res = contract.user_account.call(
+        contract.account_id(),
+        "finish_deposit_transfer",
+        &args,
+        DEFAULT_GAS,
+        0,
+    );
+    assert_error_code(&res, "ERR_PROMISE_COUNT");
+}
+
+#[test]
+fn test_upgrade() {
+    let (master_account, contract) = init();
+    call_deposit(&master_account, &contract);
+    let res = master_account.call(
+        contract.account_id(),
+        "upgrade",
+        &TOKEN_WASM_BYTES,
+        DEFAULT_GAS,
+        0,
+    );
+    assert!(!res.is_ok());
+    contract
+        .user_account
+        .call(
+            contract.account_id(),
+            "upgrade",
+            &TOKEN_WASM_BYTES,
+            DEFAULT_GAS,
+            0,
+        )
+        .assert_success();
+    // The state survives the redeployment
+    let balance = view!(contract.ft_balance_of(DEPOSITED_RECIPIENT.into())).unwrap_json::<u128>();
+    assert_eq!(balance, DEPOSITED_AMOUNT - DEPOSITED_FEE);
+    let schema_log = view!(contract.get_schema_log()).unwrap_borsh::<Vec<SchemaLogEntry>>();
+    assert_eq!(schema_log.len(), 2);
+    assert_eq!(schema_log[1].state_version, 1);
+    assert!(schema_log[1].block_height > schema_log[0].block_height);
+
+    // Only code matching the expectation can be deployed
+    call!(
+        contract.user_account,
+        contract.set_code_hash_expectation("11".repeat(32))
+    )
+    .assert_success();
+    let res = contract.user_account.call(
+        contract.account_id(),
+        "upgrade",
+        &TOKEN_WASM_BYTES,
+        DEFAULT_GAS,
+        0,
+    );
+    assert!(!res.is_ok());
+}
+
+#[test]
+fn test_timelock() {
+    let (_, contract) = init();
+    let delay = 3600 * 1_000_000_000;
+    call!(contract.user_account, contract.set_timelock_delay(delay)).assert_success();
+    let res = call!(contract.user_account, contract.set_deposit_fee(30));
+    assert!(!res.is_ok());
+
+    let args = serde_json::json!({"action": "set_deposit_fee", "deposit_fee_bps": 30});
+    let res = contract.user_account.call(
+        contract.account_id(),
+        "stage_action",
+        args.to_string().as_bytes(),
+        DEFAULT_GAS,
+        0,
+    );
+    res.assert_success();
+    let id = res.unwrap_borsh::<u64>();
+    assert_eq!(id, 0);
+    let res = call!(contract.user_account, contract.execute_action(id));
+    assert!(!res.is_ok());
+    // Lowering the delay goes through the timelock as well
+    let res = call!(contract.user_account, contract.set_timelock_delay(0));
+    assert!(!res.is_ok());
+
+    call!(contract.user_account, contract.cancel_action(id)).assert_success();
+    let res = call!(contract.user_account, contract.cancel_action(id));
+    assert!(!res.is_ok());
+    let fee_config = view!(contract.get_fee_config()).unwrap_borsh::<FeeConfig>();
+    assert_eq!(fee_config.deposit_fee_bps, 0);
+}
+
+#[test]
+fn test_webhooks() {
+    let (master_account, contract) = init();
+    let url_hash = "07".repeat(32);
+    let res = call!(
+        master_account,
+        contract.register_webhook(url_hash.clone(), vec!["deposit".into()], None)
+    );
+    assert_error_code(&res, "ERR_INSUFFICIENT_STORAGE_DEPOSIT");
+    let res = call!(
+        master_account,
+        contract.register_webhook(url_hash, vec!["deposit".into()], None),
+        deposit = to_yocto("0.1")
+    );
+    res.assert_success();
+    assert_eq!(res.unwrap_borsh::<u64>(), 0);
+    let webhook = view!(contract.get_webhook(0))
+        .unwrap_borsh::<Option<WebhookDescriptor>>()
+        .unwrap();
+    assert_eq!(webhook.owner_id, master_account.account_id());
+    assert_eq!(webhook.url_hash, [7u8; 32]);
+    assert_eq!(webhook.events, vec!["deposit".to_string()]);
+
+    // Only the webhook owner or the contract owner can remove it
+    let user = master_account.create_user("user".to_string(), to_yocto("10"));
+    let res = call!(user, contract.remove_webhook(0));
+    assert!(!res.is_ok());
+    call!(contract.user_account, contract.remove_webhook(0)).assert_success();
+    let webhooks =
+        view!(contract.get_webhooks(0, 10)).unwrap_borsh::<Vec<(u64, WebhookDescriptor)>>();
+    assert!(webhooks.is_empty());
+}
+
+#[test]
+fn test_ownership_transfer() {
+    let (master_account, contract) = init();
+    let owner_id = view!(contract.get_owner_id()).unwrap_borsh::<AccountId>();
+    assert_eq!(owner_id, CONTRACT_ACC);
+
+    let res = call!(master_account, contract.set_paused_flags(PAUSE_DEPOSIT));
+    assert!(!res.is_ok());
+
+    call!(
+        contract.user_account,
+        contract.propose_owner(master_account.account_id())
+    )
+    .assert_success();
+    // The transfer only completes once accepted
+    let owner_id = view!(contract.get_owner_id()).unwrap_borsh::<AccountId>();
+    assert_eq!(owner_id, CONTRACT_ACC);
+
+    call!(master_account, contract.accept_owner()).assert_success();
+    let owner_id = view!(contract.get_owner_id()).unwrap_borsh::<AccountId>();
+    assert_eq!(owner_id, master_account.account_id());
+
+    call!(master_account, contract.set_paused_flags(PAUSE_DEPOSIT)).assert_success();
+    let res = call!(contract.user_account, contract.set_paused_flags(0));
+    assert!(!res.is_ok());
+}